@@ -1,10 +1,16 @@
-use rand::prelude::SliceRandom;
-use std::cmp::max;
-use std::collections::HashMap;
-
+use crate::engine::disambiguation::is_pronoun;
+use crate::engine::eva::{self, Spacewalk};
+use crate::engine::outcome::{ActionResult, GameOutcome};
+use crate::engine::resolver::{self, resolve, resolve_group, Capability, LookupError, Scope};
+use crate::engine::save;
 use crate::engine::state::GameState;
-use crate::entity::item::{Containable, ItemId};
-use crate::entity::{self, Entity, EntityId};
+use crate::engine::terminal;
+use crate::entity::furniture::airlock::AirlockControls;
+use crate::entity::furniture::door::{Door, DoorState};
+use crate::entity::furniture::main_terminal::MainTerminal;
+use crate::entity::item::card::{Card, CardStatus};
+use crate::entity::item::{Openable, Wearable};
+use crate::entity::{Entity, EntityId};
 use crate::world::room::{Access, Direction, PassageType, RoomIdentifier};
 
 pub fn look(game_state: &GameState) -> String {
@@ -14,7 +20,7 @@ pub fn look(game_state: &GameState) -> String {
         output.push_str("\nYou can see the following things: ");
         for entity_id in &room_attributes.entities {
             if let Some(entity) = game_state.world.entities.get(entity_id) {
-                output.push_str(&entity.name());
+                output.push_str(entity.name());
                 output.push_str(", ");
             }
         }
@@ -27,32 +33,52 @@ pub fn look(game_state: &GameState) -> String {
 
         output.push_str("\nYou can get to: ");
         for (direction, passage_type, _room_id) in &room_attributes.connected_rooms {
-            let passage_description = match passage_type {
-                PassageType::Door => "a door to the",
-                PassageType::Free => "continue to the",
+            let door = game_state
+                .world
+                .door_at(&game_state.current_room, *direction)
+                .and_then(|door_id| game_state.world.get_door(door_id));
+            let passage_description = match (passage_type, door) {
+                (PassageType::Door, Some(door)) => door.appearance(),
+                (PassageType::Door, None) => "a door".to_string(),
+                (PassageType::Free, _) => "continue".to_string(),
+            };
+            let whereabouts = match (passage_type, direction) {
+                (PassageType::Door, Direction::Up) => "above".to_string(),
+                (PassageType::Door, Direction::Down) => "below".to_string(),
+                (_, Direction::Up | Direction::Down) => direction.to_string().to_lowercase(),
+                _ => format!("to the {}", direction.to_string().to_lowercase()),
             };
-            output.push_str(&format!("{} {}", passage_description, direction));
+            output.push_str(&format!("{} {}", passage_description, whereabouts));
             output.push_str(", ");
         }
         if output.ends_with(", ") {
             output.truncate(output.len() - 2);
         }
         output.push('.');
+        if let Some(spacewalk) = eva::describe(game_state) {
+            output.push('\n');
+            output.push_str(&spacewalk);
+        }
     } else {
         output = "Room not found in the world. Are you in space? Oh crap".into();
     }
     output
 }
 
-pub fn look_at(game_state: &GameState, obj_name: &str) -> String {
+pub fn look_at(game_state: &mut GameState, obj_name: &str) -> ActionResult {
     let obj_name = obj_name.to_lowercase();
 
-    match find_entity_in_room(game_state, &obj_name) {
-        Some(entity) => look_at_helper(game_state, entity),
-        None => match find_entity_in_inventory(game_state, &obj_name) {
-            Some(entity) => look_at_helper(game_state, entity),
-            None => format!("There is no {} here to look at.", obj_name),
+    match resolve(game_state, &obj_name, resolver::ALL, Capability::Any) {
+        Ok(entity_id) => match game_state.world.entities.get(&entity_id) {
+            Some(entity) => look_at_helper(game_state, entity.as_ref()).into(),
+            None => ActionResult::failure(format!("There is no {} here to look at.", obj_name)),
         },
+        Err(error) => lookup_failed(
+            game_state,
+            &obj_name,
+            error,
+            format!("There is no {} here to look at.", obj_name),
+        ),
     }
 }
 
@@ -63,7 +89,57 @@ fn look_at_helper(game_state: &GameState, entity: &dyn Entity) -> String {
 
     let mut output = format!("You look at the {}:\n{}", name, description);
 
-    if containable {
+    if let Some(door) = Door::as_door(entity) {
+        let state = match (door.is_jammed(), door.state()) {
+            (true, _) => "It is jammed and won't open for anyone.".to_string(),
+            (false, DoorState::Open) => "It is open.".to_string(),
+            (false, DoorState::Closed) => "It is closed.".to_string(),
+            (false, DoorState::Locked) => "It is locked.".to_string(),
+        };
+        output.push('\n');
+        output.push_str(&state);
+        if !door.is_jammed() && door.access() != Access::None {
+            output.push_str(&format!(" The card reader is marked {}.", door.access()));
+        }
+    } else if let Some(airlock) = AirlockControls::as_airlock(entity) {
+        if airlock.is_pressurized() {
+            output.push_str("\nThe display reads PRESSURIZED in steady green letters.");
+        } else {
+            output.push_str("\nThe display reads VACUUM in blinking red letters.");
+        }
+    } else if let Some(gear) = entity.as_wearable().filter(|gear| gear.tank() > 0) {
+        output.push_str(&format!(
+            "\nThe air gauge on its chest reads {} of {}.",
+            gear.air(),
+            gear.tank()
+        ));
+    } else if let Some(terminal) = MainTerminal::as_terminal(entity) {
+        if terminal.is_suspended() {
+            output.push_str(&format!(
+                "\nThe message on the display says: \"{}\"",
+                terminal.suspended()
+            ));
+        } else {
+            output.push_str(
+                "\nThe display shows the ShuttleControlOS prompt, with MAINTENANCE MODE blinking in the corner.",
+            );
+        }
+    } else if let Some(screen) = match entity.get_id() {
+        EntityId::Furniture(id) => game_state.shuttle_state.fuel_tank_screen(id),
+        _ => None,
+    } {
+        output.push('\n');
+        output.push_str(&screen);
+    } else if let Some(card) = Card::as_card(entity) {
+        if card.status() == CardStatus::Damaged {
+            output.push_str("\nIts strip is scratched and bent. No reader will take it like this.");
+        }
+    } else if entity
+        .as_openable()
+        .is_some_and(|openable| !openable.is_open())
+    {
+        output.push_str("\nIt is closed.");
+    } else if containable {
         if let Some(container) = entity.as_containable() {
             if !container.contains().is_empty() {
                 output.push_str("\nIt contains: ");
@@ -89,193 +165,697 @@ fn get_article(obj_name: &str) -> &str {
     }
 }
 
-pub fn move_in_direction(
-    game_state: &mut GameState,
-    direction: Direction,
-) -> Result<String, String> {
+pub fn move_in_direction(game_state: &mut GameState, direction: Direction) -> ActionResult {
+    if let Some(result) = eva::push_off(game_state, direction) {
+        return result;
+    }
+    let mut result = ActionResult::new();
+
     match game_state
         .world
         .get_adjacent_room(&game_state.current_room, direction)
+        .map(|(room_id, passage_type)| (room_id, *passage_type))
     {
         Some(new_room) => {
-            if (new_room.1) == &PassageType::Door {
-                if get_player_access(game_state) < *game_state.world.get_room_access(&(new_room.0))
+            if new_room.1 == PassageType::Door {
+                if let Some(door_id) = game_state
+                    .world
+                    .door_at(&game_state.current_room, direction)
                 {
-                    return Err(format!("The door beeps with an unsatisfied tone."));
+                    if let Err(error) = airlock_interlock(game_state, door_id) {
+                        return ActionResult::failure(error);
+                    }
+                    if let Err(error) = pass_door(game_state, door_id, &mut result) {
+                        return cant_go(game_state, direction, &error);
+                    }
+                    result.push(blow_out_airlock(game_state, door_id));
                 }
-                print_any!(
-                    "The door beeps with quiet acknowledgement and slides aside.",
-                    "*Shhhhht* - the door slides open.",
-                    "The door opens with no apparent effort from your side.",
-                    "The door opened so fast as if it predicted your intention."
-                );
             }
-            game_state.current_room = new_room.0;
+            enter_room(game_state, new_room.0, result)
+        }
+        None => cant_go(
+            game_state,
+            direction,
+            &format!("Can't go in the direction of {}.", direction),
+        ),
+    }
+}
 
-            if !game_state.was_current_room_visited() {
-                game_state.world.set_visited(&game_state.current_room);
-                exit_if_no_spacesuit(&game_state);
-                win_if_reached_station(&game_state);
+/// Puts the player in `room_id` and adds what they see there to `result`.
+pub fn enter_room(
+    game_state: &mut GameState,
+    room_id: RoomIdentifier,
+    mut result: ActionResult,
+) -> ActionResult {
+    game_state.current_room = room_id;
+    game_state.spacewalk = Spacewalk::start(&game_state.world, room_id);
+
+    if is_exposed(game_state) {
+        result.push("You gasp for air, and your head feels like it's exploding. You try to reach for a handrail to get back into the airlock but can't quite catch it. Everything turns black.\n\nYou are pretty sure you just died.");
+        return result.with_outcome(exposed_to_vacuum());
+    }
 
-                Ok(format!(
-                    "{}\n{}",
-                    game_state.current_room_first_thoughts(),
-                    game_state.current_room_description()
-                ))
-            } else {
-                Ok(game_state.current_room_description().to_string())
-            }
+    if !game_state.was_current_room_visited() {
+        game_state.world.set_visited(&game_state.current_room);
+        result.push(game_state.current_room_first_thoughts());
+        result.push(game_state.current_room_description());
+        if game_state.current_room == RoomIdentifier::StationAirlock {
+            return result.with_outcome(GameOutcome::Won);
+        }
+    } else {
+        result.push(game_state.current_room_description());
+    }
+    result
+}
+
+/// Lets the player through the door if it's open or the reader takes their card.
+fn pass_door(
+    game_state: &mut GameState,
+    door_id: EntityId,
+    result: &mut ActionResult,
+) -> Result<(), String> {
+    let access = get_player_access(game_state);
+    let Some(door) = game_state.world.get_door(door_id) else {
+        return Ok(());
+    };
+    if door.is_jammed() {
+        return Err("The door is jammed. It doesn't even twitch when you walk up to it.".into());
+    }
+    match door.state() {
+        DoorState::Open => Ok(()),
+        DoorState::Locked => Err("The door is locked.".into()),
+        DoorState::Closed if access < door.access() => {
+            Err("The door beeps with an unsatisfied tone.".into())
+        }
+        DoorState::Closed => {
+            result.push(any_of!(&mut game_state.rng;
+                "The door beeps with quiet acknowledgement and slides aside.",
+                "*Shhhhht* - the door slides open.",
+                "The door opens with no apparent effort from your side.",
+                "The door opened so fast as if it predicted your intention."
+            ));
+            Ok(())
         }
-        None => Err(format!("Can't go in the direction of {}.", direction)),
     }
 }
 
-pub fn exit_if_no_spacesuit(game_state: &GameState) {
-    if game_state.current_room == RoomIdentifier::OpenSpaceAirlockA
-        || game_state.current_room == RoomIdentifier::OpenSpaceAirlockB
-    {
-        if !game_state.inventory.contains(&ItemId::SpaceSuit) {
-            println!("You gasp for air, and your head feels like it's exploding. You try to reach for a handrail to get back into the airlock but can't quite catch it. Everything turns black.\n\nYou are pretty sure you just died.");
-            std::process::exit(0);
+fn cant_go(game_state: &mut GameState, direction: Direction, error: &str) -> ActionResult {
+    ActionResult::failure(any_of!(&mut game_state.rng;
+        format!("I can't go to {}: {}", direction, error),
+        format!("There's nowhere to go at {}, I think?", direction),
+        "I could try, I remember walking through solid walls worked at a certain spaceport platform to get on a spacecruiser to the nanoscience school for gifted kids... or was it in a holoseries?..".to_string()
+    ))
+}
+
+/// Out in open space, or in an airlock chamber with the air pumped out.
+pub fn is_in_vacuum(game_state: &GameState) -> bool {
+    eva::is_open_space(game_state)
+        || game_state
+            .world
+            .airlock_in(&game_state.current_room)
+            .is_some_and(|airlock| !airlock.is_pressurized())
+}
+
+/// In vacuum without anything on that keeps it out.
+fn is_exposed(game_state: &GameState) -> bool {
+    is_in_vacuum(game_state) && !worn_gear(game_state).any(|gear| gear.seals())
+}
+
+fn exposed_to_vacuum() -> GameOutcome {
+    GameOutcome::Died("exposed to vacuum without a space suit".to_string())
+}
+
+/// In vacuum the player lives off the air in the suit.
+pub fn is_breathable(game_state: &GameState) -> bool {
+    !is_in_vacuum(game_state) || worn_gear(game_state).any(|gear| gear.supplies_air())
+}
+
+/// Whether the player wears something that makes every step an effort.
+pub fn is_burdened(game_state: &GameState) -> bool {
+    worn_gear(game_state).any(|gear| gear.is_bulky())
+}
+
+fn worn_gear(game_state: &GameState) -> impl Iterator<Item = &dyn Wearable> {
+    game_state
+        .worn
+        .iter()
+        .filter_map(|item_id| game_state.world.entities.get(&EntityId::Item(*item_id)))
+        .filter_map(|entity| entity.as_wearable())
+}
+
+/// The name of what the player wears that keeps food and drink out, if anything.
+fn sealed_in(game_state: &GameState) -> Option<String> {
+    game_state
+        .worn
+        .iter()
+        .map(|item_id| EntityId::Item(*item_id))
+        .find(|id| {
+            game_state
+                .world
+                .entities
+                .get(id)
+                .and_then(|entity| entity.as_wearable())
+                .is_some_and(|gear| gear.seals())
+        })
+        .map(|id| entity_name(game_state, id).to_lowercase())
+}
+
+pub fn wear(game_state: &mut GameState, obj_name: &str) -> ActionResult {
+    let obj_name = obj_name.to_lowercase();
+    let entity_id = match resolve(game_state, &obj_name, resolver::ALL, Capability::Wearable) {
+        Ok(entity_id) => entity_id,
+        Err(error) => {
+            let missing = format!("You don't have a {} to wear.", obj_name);
+            return lookup_failed(game_state, &obj_name, error, missing);
+        }
+    };
+    let name = entity_name(game_state, entity_id).to_lowercase();
+    let EntityId::Item(item_id) = entity_id else {
+        return ActionResult::failure(format!("You can't wear the {}.", name));
+    };
+    if game_state.worn.contains(&item_id) {
+        return ActionResult::failure(format!("You're already wearing the {}.", name));
+    }
+    if !game_state.inventory.contains(&item_id) {
+        return ActionResult::failure(format!("You need to pick up the {} first.", name));
+    }
+    let Some(gear) = game_state
+        .world
+        .entities
+        .get(&entity_id)
+        .and_then(|e| e.as_wearable())
+    else {
+        return ActionResult::failure(format!("You can't wear the {}.", name));
+    };
+    let (slot, seals) = (gear.slot(), gear.seals());
+    let taken = game_state.worn.iter().copied().find(|worn_id| {
+        game_state
+            .world
+            .entities
+            .get(&EntityId::Item(*worn_id))
+            .and_then(|entity| entity.as_wearable())
+            .is_some_and(|worn| worn.slot() == slot)
+    });
+    if let Some(worn_id) = taken {
+        let worn_name = entity_name(game_state, EntityId::Item(worn_id)).to_lowercase();
+        return ActionResult::failure(format!("You'd have to take off the {} first.", worn_name));
+    }
+
+    game_state.inventory.retain(|id| *id != item_id);
+    game_state.worn.push(item_id);
+    if seals {
+        format!(
+            "You put on the {}. The seals click shut, and you hear the hiss of air.",
+            name
+        )
+        .into()
+    } else {
+        format!("You put on the {}.", name).into()
+    }
+}
+
+pub fn remove(game_state: &mut GameState, obj_name: &str) -> ActionResult {
+    let obj_name = obj_name.to_lowercase();
+    let entity_id = match resolve(game_state, &obj_name, &[Scope::Worn], Capability::Any) {
+        Ok(entity_id) => entity_id,
+        Err(error) => {
+            let missing = format!("You're not wearing a {}.", obj_name);
+            return lookup_failed(game_state, &obj_name, error, missing);
         }
+    };
+    let name = entity_name(game_state, entity_id).to_lowercase();
+    let EntityId::Item(item_id) = entity_id else {
+        return ActionResult::failure(format!("You're not wearing a {}.", obj_name));
+    };
+
+    game_state.worn.retain(|id| *id != item_id);
+    game_state.inventory.push(item_id);
+    let mut result = ActionResult::message(format!("You take off the {}.", name));
+    if is_exposed(game_state) {
+        result.push("The vacuum rushes in. Your lungs burn for a moment, and then there is nothing at all.\n\nYou are pretty sure you just died.");
+        return result.with_outcome(exposed_to_vacuum());
     }
+    result
 }
 
-pub fn win_if_reached_station(game_state: &GameState) {
-    if game_state.current_room == RoomIdentifier::StationAirlock {
-        println!(
-            "{}\n{}",
-            game_state.current_room_first_thoughts(),
-            game_state.current_room_description()
+/// The airlock chamber `door_id` leads into, and whether it's the chamber's outer door.
+fn airlock_door(game_state: &GameState, door_id: EntityId) -> Option<(RoomIdentifier, bool)> {
+    let world = &game_state.world;
+    world.airlocks().find_map(|(chamber, _)| {
+        let airlock = world.airlock_in(chamber)?;
+        if world.door_at(chamber, airlock.inner()) == Some(door_id) {
+            Some((*chamber, false))
+        } else if world.door_at(chamber, airlock.outer()) == Some(door_id) {
+            Some((*chamber, true))
+        } else {
+            None
+        }
+    })
+}
+
+/// The inner door of an airlock stays shut while the chamber is in vacuum, and the outer one
+/// while the inner one is held open.
+fn airlock_interlock(game_state: &GameState, door_id: EntityId) -> Result<(), String> {
+    let Some((chamber, outer)) = airlock_door(game_state, door_id) else {
+        return Ok(());
+    };
+    let Some(airlock) = game_state.world.airlock_in(&chamber) else {
+        return Ok(());
+    };
+    if !outer && !airlock.is_pressurized() {
+        return Err(
+            "The reader flashes red: CHAMBER DEPRESSURIZED. The door won't open until the airlock is cycled."
+                .into(),
         );
-        println!("Congrats, you won!");
-        std::process::exit(0);
     }
+    let inner_open = game_state
+        .world
+        .door_at(&chamber, airlock.inner())
+        .and_then(|id| game_state.world.get_door(id))
+        .is_some_and(|door| door.state() == DoorState::Open);
+    if outer && inner_open {
+        return Err(
+            "The reader flashes red: INNER DOOR OPEN. The outer door won't open while the inner one is."
+                .into(),
+        );
+    }
+    Ok(())
 }
 
-pub fn get_player_access(game_state: &GameState) -> Access {
-    let mut highest_access = Access::None;
-
-    for item_id in &game_state.inventory {
-        highest_access = match item_id {
-            ItemId::CaptainCard => highest_access.max(Access::A),
-            ItemId::BosunCard => highest_access.max(Access::B),
-            ItemId::AssistantCard => highest_access.max(Access::C),
-            _ => highest_access,
+/// Opening the outer door of a pressurized airlock lets the air out all at once, and it takes
+/// everything lying loose in the chamber with it. Says what happened, if anything did.
+fn blow_out_airlock(game_state: &mut GameState, door_id: EntityId) -> String {
+    let Some((chamber, true)) = airlock_door(game_state, door_id) else {
+        return String::new();
+    };
+    match game_state.world.airlock_in_mut(&chamber) {
+        Some(airlock) if airlock.is_pressurized() => airlock.set_pressurized(false),
+        _ => return String::new(),
+    }
+
+    let loose: Vec<EntityId> = game_state
+        .world
+        .get_room_entities(&chamber)
+        .map(|entities| {
+            entities
+                .iter()
+                .copied()
+                .filter(|id| matches!(id, EntityId::Item(_)))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(entities) = game_state.world.get_room_entities_mut(&chamber) {
+        entities.retain(|id| !loose.contains(id));
+    }
+
+    let mut message =
+        "With a roar, the air in the chamber rushes out through the outer door.".to_string();
+    if !loose.is_empty() {
+        let names: Vec<String> = loose
+            .iter()
+            .map(|id| entity_name(game_state, *id))
+            .collect();
+        let verbs = if names.len() == 1 {
+            "tumbles out after it and vanishes"
+        } else {
+            "tumble out after it and vanish"
+        };
+        message.push_str(&format!(
+            " The {} {} into the void.",
+            names.join(" and the "),
+            verbs
+        ));
+    }
+    message
+}
+
+pub fn cycle(game_state: &mut GameState, obj_name: Option<&str>) -> ActionResult {
+    if let Some(obj_name) = obj_name {
+        let obj_name = obj_name.to_lowercase();
+        let entity_id = match resolve(game_state, &obj_name, resolver::NEARBY, Capability::Any) {
+            Ok(entity_id) => entity_id,
+            Err(error) => {
+                let missing = format!("There's no {} here to cycle.", obj_name);
+                return lookup_failed(game_state, &obj_name, error, missing);
+            }
         };
+        let is_airlock = game_state
+            .world
+            .entities
+            .get(&entity_id)
+            .is_some_and(|entity| AirlockControls::as_airlock(entity.as_ref()).is_some());
+        if !is_airlock {
+            return ActionResult::failure(format!(
+                "You can't cycle the {}.",
+                entity_name(game_state, entity_id)
+            ));
+        }
+    }
+    let Some(airlock) = game_state.world.airlock_in(&game_state.current_room) else {
+        return ActionResult::failure("There's no airlock here to cycle.");
+    };
+    let (chamber, pressurized) = (airlock.chamber(), airlock.is_pressurized());
+    let any_open = [airlock.inner(), airlock.outer()]
+        .into_iter()
+        .filter_map(|direction| game_state.world.door_at(&chamber, direction))
+        .filter_map(|id| game_state.world.get_door(id))
+        .any(|door| door.state() == DoorState::Open);
+    if any_open {
+        return ActionResult::failure("The panel flashes red: CLOSE BOTH DOORS BEFORE CYCLING.");
+    }
+    if pressurized && !worn_gear(game_state).any(|gear| gear.seals()) {
+        return ActionResult::failure(
+            "The panel flashes red: UNPROTECTED CREW IN CHAMBER. CYCLE ABORTED.",
+        );
     }
 
-    return highest_access;
+    if let Some(airlock) = game_state.world.airlock_in_mut(&chamber) {
+        airlock.set_pressurized(!pressurized);
+    }
+    if pressurized {
+        "You press the cycle button. Pumps thump behind the walls as the air is drawn out of the chamber, until the hiss dies away and the panel reads VACUUM.".into()
+    } else {
+        "You press the cycle button. Air roars back into the chamber, and the panel turns green: PRESSURIZED.".into()
+    }
 }
 
-pub fn open(game_state: &GameState, obj: &str) -> String {
-    format!("You try to open the {}.", obj)
+/// The best access any card the player carries still gets them.
+pub fn get_player_access(game_state: &GameState) -> Access {
+    game_state
+        .inventory
+        .iter()
+        .filter_map(|item_id| game_state.world.get_card(EntityId::Item(*item_id)))
+        .filter_map(|card| card.credential())
+        .max()
+        .unwrap_or(Access::None)
 }
 
-pub fn close(game_state: &GameState, obj: &str) -> String {
-    format!("You try to close the {}.", obj)
+pub fn open(game_state: &mut GameState, obj: &str) -> ActionResult {
+    open_or_close(game_state, obj, true)
 }
 
-pub fn pick_up(game_state: &mut GameState, obj_name: &str) -> String {
-    let obj_name = obj_name.to_lowercase();
-    let article = get_article(&obj_name);
+pub fn close(game_state: &mut GameState, obj: &str) -> ActionResult {
+    open_or_close(game_state, obj, false)
+}
 
-    if let Some(entity_ref) = find_entity_in_room(game_state, &obj_name) {
-        let entity_id = entity_ref.get_id();
+fn open_or_close(game_state: &mut GameState, obj: &str, open: bool) -> ActionResult {
+    let obj_name = obj.to_lowercase();
+    let entity_id = match resolve(game_state, &obj_name, resolver::ALL, Capability::Openable) {
+        Ok(entity_id) => entity_id,
+        Err(error) => {
+            let verb = if open { "open" } else { "close" };
+            let missing = format!("There's no {} here that you could {}.", obj_name, verb);
+            return lookup_failed(game_state, &obj_name, error, missing);
+        }
+    };
+    let name = entity_name(game_state, entity_id);
+    if game_state.world.get_door(entity_id).is_some() {
+        return open_or_close_door(game_state, entity_id, &name, open);
+    }
 
-        // Add to the inventory
-        match entity_id {
-            EntityId::Item(item_id) => {
-                game_state.inventory.push(item_id);
+    let Some(openable) = game_state
+        .world
+        .entities
+        .get_mut(&entity_id)
+        .and_then(|entity| entity.as_openable_mut())
+    else {
+        return ActionResult::failure(format!("The {} doesn't open or close.", name));
+    };
+    if let Err(e) = if open {
+        openable.open()
+    } else {
+        openable.close()
+    } {
+        return ActionResult::failure(e);
+    }
 
-                if let Some(entity) = game_state.world.entities.get(&entity_id) {
-                    // Remove from the room
-                    if let Some(room) = game_state.world.rooms.get_mut(&game_state.current_room) {
-                        room.entities.retain(|&e| e != entity_id);
-                    }
-                    format!(
-                        "You pick up {}{} and look at it: {}",
-                        article,
-                        entity.name(),
-                        entity.description()
-                    )
-                } else {
-                    format!("There seems to be a problem picking up the {}.", obj_name)
-                }
-            }
-            _ => format!("You can't pick up the {}.", obj_name),
-        }
+    if !open {
+        return format!("You close the {}.", name).into();
+    }
+    let contents = resolver::entities_in(game_state, &[Scope::Container(entity_id)]);
+    if contents.is_empty() {
+        format!("You open the {}. It's empty.", name).into()
     } else {
-        format!("There is no {} here.", obj_name)
+        let names: Vec<String> = contents
+            .iter()
+            .map(|id| entity_name(game_state, *id))
+            .collect();
+        format!(
+            "You open the {}. Inside you see: {}.",
+            name,
+            names.join(", ")
+        )
+        .into()
+    }
+}
+
+/// Opening a door takes a card the reader accepts, and then it stays open until closed again.
+fn open_or_close_door(
+    game_state: &mut GameState,
+    door_id: EntityId,
+    name: &str,
+    open: bool,
+) -> ActionResult {
+    let access = get_player_access(game_state);
+    let Some(door) = game_state.world.get_door(door_id) else {
+        return ActionResult::failure(format!("The {} doesn't open or close.", name));
+    };
+    if open && !door.is_jammed() && door.state() != DoorState::Open {
+        if access < door.access() {
+            return ActionResult::failure("The door beeps with an unsatisfied tone.");
+        }
+        let opened = if door.state() == DoorState::Locked {
+            format!(
+                "The reader takes your card, and the {} unlocks and slides open.",
+                name
+            )
+        } else {
+            format!("You open the {}. It stays open.", name)
+        };
+        return open_door(game_state, door_id, opened);
+    }
+    let Some(door) = game_state.world.get_door_mut(door_id) else {
+        return ActionResult::failure(format!("The {} doesn't open or close.", name));
+    };
+    let changed = if open { door.open() } else { door.close() };
+    match changed {
+        Err(e) => ActionResult::failure(e),
+        Ok(()) => format!("You close the {}.", name).into(),
+    }
+}
+
+/// Opens a door whose reader took the card, unless it's an airlock door the interlock keeps
+/// shut. Opening the outer door of a pressurized airlock blows the chamber out.
+fn open_door(game_state: &mut GameState, door_id: EntityId, opened: String) -> ActionResult {
+    if let Err(error) = airlock_interlock(game_state, door_id) {
+        return ActionResult::failure(error);
     }
+    if let Some(door) = game_state.world.get_door_mut(door_id) {
+        door.set_state(DoorState::Open);
+    }
+    let mut result = ActionResult::message(opened);
+    result.push(blow_out_airlock(game_state, door_id));
+    if is_exposed(game_state) {
+        result.push("The blast tears you off your feet and flings you out after it. Your lungs burn for a moment, and then there is nothing at all.\n\nYou are pretty sure you just died.");
+        return result.with_outcome(exposed_to_vacuum());
+    }
+    result
+}
+
+/// Why nothing can be taken out of or put into the container, if it's closed.
+fn closed_container(game_state: &GameState, container_id: EntityId) -> Option<String> {
+    game_state
+        .world
+        .is_closed(container_id)
+        .then(|| format!("The {} is closed.", entity_name(game_state, container_id)))
 }
 
-pub fn drop(game_state: &mut GameState, obj_name: &str) -> String {
+pub fn pick_up(game_state: &mut GameState, obj_name: &str) -> ActionResult {
     let obj_name = obj_name.to_lowercase();
-    let article = get_article(&obj_name);
 
-    if let Some(entity_ref) = find_entity_in_inventory(game_state, &obj_name) {
-        let entity_id = entity_ref.get_id();
+    if let Some(entity_ids) = resolve_group(game_state, &obj_name, &[Scope::Room]) {
+        let items = entity_ids
+            .into_iter()
+            .filter(|id| matches!(id, EntityId::Item(_)))
+            .collect();
+        return for_each(
+            game_state,
+            items,
+            "There's nothing here you can take.",
+            pick_up_entity,
+        );
+    }
+
+    match resolve(game_state, &obj_name, &[Scope::Room], Capability::Any) {
+        Ok(entity_id) => pick_up_entity(game_state, entity_id).into(),
+        Err(error) => lookup_failed(
+            game_state,
+            &obj_name,
+            error,
+            format!("There is no {} here.", obj_name),
+        ),
+    }
+}
 
-        if let EntityId::Item(item_id) = entity_id {
-            // Remove from the inventory
-            game_state.inventory.retain(|&e| e != item_id);
+fn pick_up_entity(game_state: &mut GameState, entity_id: EntityId) -> Result<String, String> {
+    let name = entity_name(game_state, entity_id);
+    let article = get_article(&name);
 
-            // Add to the room
-            if let Some(room) = game_state.world.rooms.get_mut(&game_state.current_room) {
-                room.entities.push(entity_id);
-            }
+    // Add to the inventory
+    match entity_id {
+        EntityId::Item(item_id) => {
+            game_state.inventory.push(item_id);
 
             if let Some(entity) = game_state.world.entities.get(&entity_id) {
-                return format!("You've dropped {}{}.", article, obj_name);
+                // Remove from the room
+                if let Some(room) = game_state.world.rooms.get_mut(&game_state.current_room) {
+                    room.entities.retain(|&e| e != entity_id);
+                }
+                Ok(format!(
+                    "You pick up {}{} and look at it: {}",
+                    article,
+                    entity.name(),
+                    entity.description()
+                ))
             } else {
-                return "There seems to be a problem dropping the item.".to_string();
+                Err(format!(
+                    "There seems to be a problem picking up the {}.",
+                    name
+                ))
             }
-        } else {
-            return format!("You can't drop the {}.", obj_name);
         }
+        _ => Err(format!("You can't pick up the {}.", name)),
+    }
+}
+
+pub fn drop(game_state: &mut GameState, obj_name: &str) -> ActionResult {
+    let obj_name = obj_name.to_lowercase();
+
+    if let Some(entity_ids) = resolve_group(game_state, &obj_name, &[Scope::Inventory]) {
+        return for_each(
+            game_state,
+            entity_ids,
+            "You don't have anything to drop.",
+            drop_entity,
+        );
+    }
+
+    match resolve(game_state, &obj_name, &[Scope::Inventory], Capability::Any) {
+        Ok(entity_id) => drop_entity(game_state, entity_id).into(),
+        Err(error) => lookup_failed(
+            game_state,
+            &obj_name,
+            error,
+            format!("You don't have a {} to drop.", obj_name),
+        ),
+    }
+}
+
+fn drop_entity(game_state: &mut GameState, entity_id: EntityId) -> Result<String, String> {
+    let name = entity_name(game_state, entity_id);
+    let article = get_article(&name);
+
+    if let EntityId::Item(item_id) = entity_id {
+        // Remove from the inventory
+        game_state.inventory.retain(|&e| e != item_id);
+
+        // Add to the room
+        if let Some(room) = game_state.world.rooms.get_mut(&game_state.current_room) {
+            room.entities.push(entity_id);
+        }
+
+        Ok(format!("You've dropped {}{}.", article, name))
     } else {
-        format!("You don't have a {} to drop.", obj_name)
+        Err(format!("You can't drop the {}.", name))
     }
 }
 
-pub fn put_into(game_state: &mut GameState, obj_name: &str, cont_name: &str) -> String {
+pub fn put_into(game_state: &mut GameState, obj_name: &str, cont_name: &str) -> ActionResult {
     let obj_name = obj_name.to_lowercase();
     let cont_name = cont_name.to_lowercase();
 
+    // Find the container in the room or inventory
+    let find_container = |game_state: &mut GameState| {
+        resolve(
+            game_state,
+            &cont_name,
+            resolver::NEARBY,
+            Capability::Containable,
+        )
+        .map_err(|error| {
+            let missing = format!("There's no {} to put things into.", cont_name);
+            lookup_failed(game_state, &cont_name, error, missing)
+        })
+        .and_then(
+            |cont_entity_id| match closed_container(game_state, cont_entity_id) {
+                Some(closed) => Err(ActionResult::failure(closed)),
+                None => Ok(cont_entity_id),
+            },
+        )
+    };
+
+    if let Some(entity_ids) = resolve_group(game_state, &obj_name, &[Scope::Inventory]) {
+        let cont_entity_id = match find_container(game_state) {
+            Ok(cont_entity_id) => cont_entity_id,
+            Err(failure) => return failure,
+        };
+        let entity_ids = entity_ids
+            .into_iter()
+            .filter(|&id| id != cont_entity_id)
+            .collect();
+        return for_each(
+            game_state,
+            entity_ids,
+            "You don't have anything to put there.",
+            |game_state, obj_id| put_entity_into(game_state, obj_id, cont_entity_id),
+        );
+    }
+
     // Find the object in the inventory
-    if let Some(obj_entity_ref) = find_entity_in_inventory(game_state, &obj_name) {
-        let obj_id = obj_entity_ref.get_id();
-
-        // Find the container in the room or inventory
-        if let Some((_, cont_entity_id)) = find_containable_entity(game_state, &cont_name) {
-            // Check if it's the same
-            if cont_entity_id == obj_id {
-                return "You can't put something into itself.".to_string();
-            }
+    let obj_id = match resolve(game_state, &obj_name, &[Scope::Inventory], Capability::Any) {
+        Ok(obj_id) => obj_id,
+        Err(error) => {
+            let missing = format!("You don't have {}.", obj_name);
+            return lookup_failed(game_state, &obj_name, error, missing);
+        }
+    };
+    let cont_entity_id = match find_container(game_state) {
+        Ok(cont_entity_id) => cont_entity_id,
+        Err(failure) => return failure,
+    };
+
+    // Check if it's the same
+    if cont_entity_id == obj_id {
+        return ActionResult::failure("You can't put something into itself.");
+    }
 
-            if let Some(container) = game_state.world.get_containable_mut(cont_entity_id) {
-                if container.can_contain(obj_id) {
-                    game_state
-                        .inventory
-                        .retain(|&e| EntityId::Item(e) != obj_id);
+    let output = put_entity_into(game_state, obj_id, cont_entity_id);
+    game_state.mentioned = vec![obj_id];
+    output.into()
+}
 
-                    if let Err(e) = container.put(obj_id) {
-                        return e;
-                    }
+fn put_entity_into(
+    game_state: &mut GameState,
+    obj_id: EntityId,
+    cont_entity_id: EntityId,
+) -> Result<String, String> {
+    let obj_name = entity_name(game_state, obj_id);
+    let cont_name = entity_name(game_state, cont_entity_id);
 
-                    return format!("You put {} into {}.", obj_name, cont_name);
-                } else {
-                    return format!("The {} cannot contain {}.", cont_name, obj_name);
-                }
-            } else {
-                return format!("{} is not a container.", cont_name);
-            }
+    if let Some(container) = game_state.world.get_containable_mut(cont_entity_id) {
+        if container.can_contain(obj_id) {
+            game_state
+                .inventory
+                .retain(|&e| EntityId::Item(e) != obj_id);
+
+            container.put(obj_id)?;
+
+            Ok(format!("You put {} into {}.", obj_name, cont_name))
         } else {
-            return format!("There's no {} to put things into.", cont_name);
+            Err(format!("The {} cannot contain {}.", cont_name, obj_name))
         }
     } else {
-        return format!("You don't have {}.", obj_name);
+        Err(format!("{} is not a container.", cont_name))
     }
 }
 
@@ -283,74 +863,81 @@ pub fn take_from_container(
     game_state: &mut GameState,
     item_name: &str,
     container_name: &str,
-) -> String {
+) -> ActionResult {
     let item_name = item_name.to_lowercase();
     let container_name = container_name.to_lowercase();
 
     // Find the container in the room or inventory
-    if let Some((_, container_id)) = find_containable_entity(game_state, &container_name) {
-        // Get IDs of the entities inside
-        let contained_ids: Vec<EntityId> =
-            if let Some(container) = game_state.world.get_containable(container_id) {
-                container.contains().to_vec()
-            } else {
-                return format!("{} is not a container.", container_name);
-            };
+    let container_id = match resolve(
+        game_state,
+        &container_name,
+        resolver::NEARBY,
+        Capability::Containable,
+    ) {
+        Ok(container_id) => container_id,
+        Err(error) => {
+            let missing = format!("There is no {} here to take things from.", container_name);
+            return lookup_failed(game_state, &container_name, error, missing);
+        }
+    };
+    if let Some(closed) = closed_container(game_state, container_id) {
+        return ActionResult::failure(closed);
+    }
 
-        // Find the ID of the entity to take
-        let entity_id_to_take = contained_ids
-            .iter()
-            .find(|&&id| {
-                if let Some(entity) = game_state.world.entities.get(&id) {
-                    let name = entity.name().to_lowercase();
-                    let aliases = entity
-                        .aliases()
-                        .iter()
-                        .map(|alias| alias.to_lowercase())
-                        .collect::<Vec<String>>();
-                    name == item_name || aliases.contains(&item_name)
+    let inside = [Scope::Container(container_id)];
+    if let Some(entity_ids) = resolve_group(game_state, &item_name, &inside) {
+        let nothing = format!("There's nothing in the {}.", container_name);
+        return for_each(game_state, entity_ids, &nothing, |game_state, entity_id| {
+            take_entity_from(game_state, entity_id, container_id)
+        });
+    }
+
+    // Find the ID of the entity to take
+    match resolve(game_state, &item_name, &inside, Capability::Any) {
+        Ok(entity_id) => take_entity_from(game_state, entity_id, container_id).into(),
+        Err(error) => {
+            let missing = format!("The {} is not in the {}.", item_name, container_name);
+            lookup_failed(game_state, &item_name, error, missing)
+        }
+    }
+}
+
+fn take_entity_from(
+    game_state: &mut GameState,
+    entity_id: EntityId,
+    container_id: EntityId,
+) -> Result<String, String> {
+    let item_name = entity_name(game_state, entity_id);
+    let container_name = entity_name(game_state, container_id);
+
+    // Attempt to take it from the container
+    if let Some(container) = game_state.world.get_containable_mut(container_id) {
+        match container.remove(entity_id) {
+            Ok(_) => {
+                // If an item, take into inventory
+                if let EntityId::Item(item_id) = entity_id {
+                    game_state.inventory.push(item_id);
+                    Ok(format!("You take {} from {}.", item_name, container_name))
                 } else {
-                    false
-                }
-            })
-            .copied();
-
-        // If the entity is found, attempt to take it from the container
-        if let Some(entity_id) = entity_id_to_take {
-            if let Some(container) = game_state.world.get_containable_mut(container_id) {
-                match container.remove(entity_id) {
-                    Ok(_) => {
-                        // If an item, take into inventory
-                        if let EntityId::Item(item_id) = entity_id {
-                            game_state.inventory.push(item_id);
-                            return format!("You take {} from {}.", item_name, container_name);
-                        } else {
-                            // If not an item, drop on the floor
-                            if let Some(entities) = game_state
-                                .world
-                                .get_room_entities_mut(&game_state.current_room)
-                            {
-                                entities.push(entity_id);
-                                return format!(
-                                    "You take {} from {}, but it's not an item you can carry.",
-                                    item_name, container_name
-                                );
-                            } else {
-                                return "Could not find the current room to return the entity."
-                                    .to_string();
-                            }
-                        }
+                    // If not an item, drop on the floor
+                    if let Some(entities) = game_state
+                        .world
+                        .get_room_entities_mut(&game_state.current_room)
+                    {
+                        entities.push(entity_id);
+                        Ok(format!(
+                            "You take {} from {}, but it's not an item you can carry.",
+                            item_name, container_name
+                        ))
+                    } else {
+                        Err("Could not find the current room to return the entity.".to_string())
                     }
-                    Err(e) => e,
                 }
-            } else {
-                format!("{} is not a container.", container_name)
             }
-        } else {
-            format!("The {} is not in the {}.", item_name, container_name)
+            Err(e) => Err(e),
         }
     } else {
-        format!("There is no {} here to take things from.", container_name)
+        Err(format!("{} is not a container.", container_name))
     }
 }
 
@@ -460,288 +1047,311 @@ pub fn take_from_container(
 //     }
 // }
 
-pub fn eat(game_state: &mut GameState, item_name: &str) -> String {
+pub fn eat(game_state: &mut GameState, item_name: &str) -> ActionResult {
     let item_name = item_name.to_lowercase();
-    let article = get_article(&item_name);
-    if let Some(food_entity_id) = find_food_in_inventory(game_state, &item_name) {
-        if let Some(food_item) = game_state.world.get_edible_mut(food_entity_id) {
-            match food_item.eat() {
-                Ok(_) => {
-                    // Remove from inventory
-                    if let EntityId::Item(item_id) = food_entity_id {
-                        game_state.inventory.retain(|&id| id != item_id);
-                        format!("You eat the {}. Yum!", item_name)
-                    } else {
-                        format!("You can't eat the {}.", item_name)
-                    }
-                }
-                Err(e) => e.to_string(),
-            }
-        } else {
-            format!("You can't eat the {}.", item_name)
+    let food_entity_id = match resolve(game_state, &item_name, resolver::ALL, Capability::Edible) {
+        Ok(food_entity_id) => food_entity_id,
+        Err(error) => {
+            let missing = format!("You don't have any {} to eat.", item_name);
+            return lookup_failed(game_state, &item_name, error, missing);
         }
-    } else {
-        format!("You don't have any {} to eat.", item_name)
+    };
+
+    let item_name = entity_name(game_state, food_entity_id);
+    if let Some(gear) = sealed_in(game_state) {
+        return ActionResult::failure(format!(
+            "You can't eat anything with the {} sealed around you.",
+            gear
+        ));
     }
-}
 
-pub fn read(game_state: &mut GameState, item_name: &str) -> String {
-    let item_name = item_name.to_lowercase();
-    let article = get_article(&item_name);
-    if let Some(readable_entity_id) = find_readable_in_inventory(game_state, &item_name) {
-        if let Some(readable_item) = game_state.world.get_readable_mut(readable_entity_id) {
-            match readable_item.read() {
-                Ok(contents) => {
-                    format!("You read the {}: {}", item_name, contents)
-                }
-                Err(e) => e.to_string(),
+    if let Some(food_item) = game_state.world.get_edible_mut(food_entity_id) {
+        match food_item.eat() {
+            Ok(nourishment) => {
+                // It may have been in the inventory, on the floor or on the counter
+                remove_from_reach(game_state, food_entity_id);
+                game_state.vitals.nourish(nourishment);
+                format!("You eat the {}. Yum!", item_name).into()
             }
-        } else {
-            format!("There's nothing interesting written on the {}.", item_name)
+            Err(e) => ActionResult::failure(e.to_string()),
         }
     } else {
-        format!("You don't see a {} to read.", item_name)
+        ActionResult::failure(format!("You can't eat the {}.", item_name))
     }
 }
 
-pub fn r#use(game_state: &mut GameState, item_name: &str) -> String {
+pub fn drink(game_state: &mut GameState, item_name: &str) -> ActionResult {
     let item_name = item_name.to_lowercase();
-    let article = get_article(&item_name);
-    if let Some(usable_entity_id) = find_usable_entity_in_room(game_state, &item_name) {
-        if let Some(usable_item) = game_state.world.get_usable_mut(usable_entity_id) {
-            match usable_item.r#use() {
-                Ok(contents) => {
-                    format!("You use the {}: {}", item_name, contents)
-                }
-                Err(e) => e.to_string(),
+    let drink_entity_id =
+        match resolve(game_state, &item_name, resolver::ALL, Capability::Drinkable) {
+            Ok(drink_entity_id) => drink_entity_id,
+            Err(error) => {
+                let missing = format!("There's no {} to drink here.", item_name);
+                return lookup_failed(game_state, &item_name, error, missing);
             }
-        } else {
-            format!("Cannot use {}.", item_name)
-        }
-    } else {
-        format!("You don't see a {} to use.", item_name)
-    }
-}
+        };
 
-pub fn enter(game_state: &mut GameState, command: &str) -> String {
-    if game_state.current_room == RoomIdentifier::Bridge {
-        match game_state.enter_shuttle_command(command) {
-            Ok(output) => format!("{}", output),
-            Err(err) => format!("{}", err),
-        }
-    } else {
-        format!("You don't know where to enter this command.")
+    let item_name = entity_name(game_state, drink_entity_id);
+    if let Some(gear) = sealed_in(game_state) {
+        return ActionResult::failure(format!(
+            "You can't drink anything with the {} sealed around you.",
+            gear
+        ));
     }
-}
 
-fn find_entity_in_room<'a>(game_state: &'a GameState, obj_name: &str) -> Option<&'a dyn Entity> {
-    let search_name = obj_name.to_lowercase();
-
-    if let Some(room_entity_ids) = game_state.current_room_entities() {
-        for entity_id in room_entity_ids {
-            if let Some(entity) = game_state.world.entities.get(entity_id) {
-                //eprintln!("DEBUG: Comparing: '{}' with '{}'", entity.name().to_lowercase(), search_name);
-                if entity.name().to_lowercase() == search_name {
-                    return Some(entity.as_ref());
-                }
-                for alt_name in entity.aliases() {
-                    //eprintln!("DEBUG: Comparing: '{}' with '{}'", alt_name, search_name);
-                    if *alt_name == search_name {
-                        return Some(entity.as_ref());
-                    }
+    match game_state.world.get_drinkable_mut(drink_entity_id) {
+        Some(drink) => match drink.drink() {
+            Ok(nourishment) => {
+                game_state.vitals.nourish(nourishment);
+                // A bottle is gone once it's drunk, a tap keeps running
+                if let EntityId::Item(_) = drink_entity_id {
+                    remove_from_reach(game_state, drink_entity_id);
+                    format!("You drink the {}. Refreshing!", item_name).into()
+                } else {
+                    format!("You drink from the {}. Refreshing!", item_name).into()
                 }
             }
-        }
+            Err(e) => ActionResult::failure(e.to_string()),
+        },
+        None => ActionResult::failure(format!("You can't drink the {}.", item_name)),
     }
-
-    None
 }
 
-fn find_entity_in_inventory<'a>(
-    game_state: &'a GameState,
-    obj_name: &str,
-) -> Option<&'a dyn Entity> {
-    let search_name = obj_name.to_lowercase();
-
-    for item_id in &game_state.inventory {
-        if let Some(entity) = game_state.world.entities.get(&EntityId::Item(*item_id)) {
-            //eprintln!("DEBUG: Comparing: '{}' with '{}'", entity.name().to_lowercase(), search_name);
-            if entity.name().to_lowercase() == search_name {
-                return Some(entity.as_ref());
-            }
-            for alt_name in entity.aliases() {
-                //eprintln!("DEBUG: Comparing: '{}' with '{}'", alt_name, search_name);
-                if *alt_name == search_name {
-                    return Some(entity.as_ref());
-                }
+fn remove_from_reach(game_state: &mut GameState, entity_id: EntityId) {
+    if let EntityId::Item(item_id) = entity_id {
+        game_state.inventory.retain(|&id| id != item_id);
+    }
+    if let Some(entities) = game_state
+        .world
+        .get_room_entities_mut(&game_state.current_room)
+    {
+        entities.retain(|&id| id != entity_id);
+    }
+    for container_id in resolver::entities_in(game_state, resolver::NEARBY) {
+        if let Some(container) = game_state.world.get_containable_mut(container_id) {
+            if container.contains().contains(&entity_id) {
+                let _ = container.remove(entity_id);
             }
         }
     }
-
-    None
 }
 
-fn find_containable_entity<'a>(
-    game_state: &'a GameState,
-    name: &str,
-) -> Option<(&'a dyn Containable, EntityId)> {
-    let search_name = name.to_lowercase();
-
-    if let Some(entity_id) = find_containable_entity_in_room(game_state, &search_name) {
-        if let Some(entity) = game_state.world.entities.get(&entity_id) {
-            if let Some(containable) = entity.as_containable() {
-                return Some((containable, entity_id));
+pub fn read(game_state: &mut GameState, item_name: &str) -> ActionResult {
+    let item_name = item_name.to_lowercase();
+    let readable_entity_id =
+        match resolve(game_state, &item_name, resolver::ALL, Capability::Readable) {
+            Ok(readable_entity_id) => readable_entity_id,
+            Err(error) => {
+                let missing = format!("You don't see a {} to read.", item_name);
+                return lookup_failed(game_state, &item_name, error, missing);
             }
-        }
-    }
+        };
+    let item_name = entity_name(game_state, readable_entity_id);
 
-    if let Some(entity_id) = find_containable_entity_in_inventory(game_state, &search_name) {
-        if let Some(entity) = game_state.world.entities.get(&entity_id) {
-            if let Some(containable) = entity.as_containable() {
-                return Some((containable, entity_id));
-            }
+    if let Some(readable_item) = game_state.world.get_readable_mut(readable_entity_id) {
+        match readable_item.read() {
+            Ok(contents) => format!("You read the {}: {}", item_name, contents).into(),
+            Err(e) => ActionResult::failure(e.to_string()),
         }
+    } else {
+        ActionResult::failure(format!(
+            "There's nothing interesting written on the {}.",
+            item_name
+        ))
     }
-
-    None
 }
 
-fn find_containable_entity_in_room(game_state: &GameState, cont_name: &str) -> Option<EntityId> {
-    let search_name = cont_name.to_lowercase();
+pub fn r#use(game_state: &mut GameState, item_name: &str) -> ActionResult {
+    let item_name = item_name.to_lowercase();
+    let usable_entity_id = match resolve(game_state, &item_name, resolver::ALL, Capability::Usable)
+    {
+        Ok(usable_entity_id) => usable_entity_id,
+        Err(error) => {
+            let missing = format!("You don't see a {} to use.", item_name);
+            return lookup_failed(game_state, &item_name, error, missing);
+        }
+    };
+    if terminal::is_terminal(game_state, usable_entity_id) {
+        return terminal::log_in(game_state, usable_entity_id).into();
+    }
+    let item_name = entity_name(game_state, usable_entity_id);
 
-    if let Some(room_entity_ids) = game_state.current_room_entities() {
-        for entity_id in room_entity_ids {
-            if let Some(entity) = game_state.world.entities.get(entity_id) {
-                //eprintln!("DEBUG: Comparing: '{}' with '{}'", entity.name().to_lowercase(), search_name);
-                if entity.name().to_lowercase() == search_name {
-                    if entity.as_containable().is_some() {
-                        //eprintln!("It's containable!");
-                        return Some(*entity_id);
-                    } else {
-                        //eprintln!("It's not containable!");
-                    }
-                }
-                for alt_name in entity.aliases() {
-                    //eprintln!("DEBUG: Comparing: '{}' with '{}'", alt_name, search_name);
-                    if *alt_name == search_name {
-                        if entity.as_containable().is_some() {
-                            //eprintln!("It's containable!");
-                            return Some(*entity_id);
-                        } else {
-                            //eprintln!("It's not containable!");
-                        }
-                    }
-                }
-            }
+    if let Some(usable_item) = game_state.world.get_usable_mut(usable_entity_id) {
+        match usable_item.r#use() {
+            Ok(contents) => format!("You use the {}: {}", item_name, contents).into(),
+            Err(e) => ActionResult::failure(e.to_string()),
         }
+    } else {
+        ActionResult::failure(format!("Cannot use {}.", item_name))
     }
-    None
 }
 
-fn find_containable_entity_in_inventory(
-    game_state: &GameState,
-    cont_name: &str,
-) -> Option<EntityId> {
-    let search_name = cont_name.to_lowercase();
-
-    for item_id in &game_state.inventory {
-        let entity_id = EntityId::Item(*item_id);
-        if let Some(entity) = game_state.world.entities.get(&EntityId::Item(*item_id)) {
-            //eprintln!("DEBUG: Comparing: '{}' with '{}'", entity.name().to_lowercase(), search_name);
-            if entity.name().to_lowercase() == search_name {
-                if entity.as_containable().is_some() {
-                    return Some(entity_id);
-                }
-            }
-            for alt_name in entity.aliases() {
-                //eprintln!("DEBUG: Comparing: '{}' with '{}'", alt_name, search_name);
-                if *alt_name == search_name {
-                    return Some(entity_id);
-                }
-            }
+/// Uses one thing on another. So far that's swiping a card at a door's reader, which opens the
+/// door if the reader accepts the card.
+pub fn use_on(game_state: &mut GameState, item_name: &str, target_name: &str) -> ActionResult {
+    let item_name = item_name.to_lowercase();
+    let target_name = target_name.to_lowercase();
+    let card_id = match resolve(game_state, &item_name, &[Scope::Inventory], Capability::Any) {
+        Ok(card_id) if game_state.world.get_card(card_id).is_some() => card_id,
+        Ok(other_id) => {
+            return ActionResult::failure(format!(
+                "You can't use the {} on anything.",
+                entity_name(game_state, other_id).to_lowercase()
+            ));
+        }
+        // Readers only take cards the player holds, like walking through a door does.
+        Err(LookupError::OutOfScope(entity_id)) => {
+            return ActionResult::failure(format!(
+                "You'd have to pick up the {} first.",
+                entity_name(game_state, entity_id).to_lowercase()
+            ));
+        }
+        Err(error) => {
+            let missing = format!("You don't have a {}.", item_name);
+            return lookup_failed(game_state, &item_name, error, missing);
+        }
+    };
+    let door_id = match resolve(game_state, &target_name, resolver::NEARBY, Capability::Any) {
+        Ok(door_id) if game_state.world.get_door(door_id).is_some() => door_id,
+        Ok(other_id) => {
+            return ActionResult::failure(format!(
+                "There's no card reader on the {}.",
+                entity_name(game_state, other_id).to_lowercase()
+            ));
+        }
+        Err(error) => {
+            let missing = format!("There's no {} here.", target_name);
+            return lookup_failed(game_state, &target_name, error, missing);
+        }
+    };
+    let door_name = entity_name(game_state, door_id).to_lowercase();
+
+    let Some(card) = game_state.world.get_card(card_id) else {
+        return ActionResult::failure(format!("You don't have a {}.", item_name));
+    };
+    let status = card.status();
+    let access = card.access();
+    let Some(door) = game_state.world.get_door_mut(door_id) else {
+        return ActionResult::failure(format!("There's no card reader on the {}.", door_name));
+    };
+    if door.is_jammed() {
+        return ActionResult::failure("Sparks fly from the reader, but nothing else happens.");
+    }
+    match status {
+        CardStatus::Damaged => {
+            return ActionResult::failure(
+                "The reader can't make anything of the card and blinks an error.",
+            )
         }
+        CardStatus::Revoked => {
+            return ActionResult::failure("The reader flashes red: ACCESS REVOKED.");
+        }
+        CardStatus::Valid => {}
+    }
+    if access < door.access() {
+        return ActionResult::failure("The door beeps with an unsatisfied tone.");
+    }
+    if door.state() == DoorState::Open {
+        return ActionResult::failure(format!(
+            "The reader blinks green, but the {} is already open.",
+            door_name
+        ));
     }
-    None
+    let opened = format!(
+        "The reader blinks green, and the {} slides open.",
+        door_name
+    );
+    open_door(game_state, door_id, opened)
 }
 
-fn find_food_in_inventory(game_state: &GameState, food_name: &str) -> Option<EntityId> {
-    let search_name = food_name.to_lowercase();
+pub fn save(game_state: &GameState, slot: Option<&str>) -> String {
+    match save::save(game_state, slot) {
+        Ok(slot) => format!("Game saved to slot '{}'.", slot),
+        Err(err) => err,
+    }
+}
 
-    for item_id in &game_state.inventory {
-        let entity_id = EntityId::Item(*item_id);
-        if let Some(entity) = game_state.world.entities.get(&EntityId::Item(*item_id)) {
-            //eprintln!("DEBUG: Comparing: '{}' with '{}'", entity.name().to_lowercase(), search_name);
-            if entity.name().to_lowercase() == search_name {
-                if entity.as_edible().is_some() {
-                    return Some(entity_id);
-                }
-            }
-            for alt_name in entity.aliases() {
-                //eprintln!("DEBUG: Comparing: '{}' with '{}'", alt_name, search_name);
-                if *alt_name == search_name {
-                    if entity.as_edible().is_some() {
-                        return Some(entity_id);
-                    }
-                }
-            }
+pub fn load(game_state: &mut GameState, slot: Option<&str>) -> String {
+    match save::load(slot, game_state.data_dir.as_deref()) {
+        Ok(loaded) => {
+            *game_state = loaded;
+            format!("Game loaded.\n{}", game_state.current_room_description())
         }
+        Err(err) => err,
     }
-    None
 }
 
-fn find_readable_in_inventory(game_state: &GameState, readable_name: &str) -> Option<EntityId> {
-    let search_name = readable_name.to_lowercase();
+pub fn enter(game_state: &mut GameState, command: &str) -> ActionResult {
+    match terminal::current(game_state) {
+        Some(terminal) => game_state.enter_shuttle_command(terminal, command),
+        None => ActionResult::failure("You don't know where to enter this command."),
+    }
+}
 
-    for item_id in &game_state.inventory {
-        let entity_id = EntityId::Item(*item_id);
-        if let Some(entity) = game_state.world.entities.get(&EntityId::Item(*item_id)) {
-            //eprintln!("DEBUG: Comparing: '{}' with '{}'", entity.name().to_lowercase(), search_name);
-            if entity.name().to_lowercase() == search_name {
-                if entity.as_readable().is_some() {
-                    return Some(entity_id);
-                }
-            }
-            for alt_name in entity.aliases() {
-                //eprintln!("DEBUG: Comparing: '{}' with '{}'", alt_name, search_name);
-                if *alt_name == search_name {
-                    if entity.as_readable().is_some() {
-                        return Some(entity_id);
-                    }
-                }
+fn lookup_failed(
+    game_state: &mut GameState,
+    name: &str,
+    error: LookupError,
+    missing: String,
+) -> ActionResult {
+    let message = match error {
+        LookupError::Missing(_) if is_pronoun(name) => {
+            format!("You're not sure what \"{}\" refers to.", name)
+        }
+        LookupError::Missing(Some(suggestion)) => {
+            format!("{} Did you mean the {}?", missing, suggestion)
+        }
+        LookupError::Missing(None) => missing,
+        LookupError::OutOfScope(entity_id) => {
+            let name = entity_name(game_state, entity_id);
+            if resolver::entities_in(game_state, resolver::ALL).contains(&entity_id) {
+                format!("You can't do that with the {}.", name)
+            } else {
+                format!("The {} isn't here.", name)
             }
         }
-    }
-    None
+        LookupError::Ambiguous(candidates) => {
+            game_state
+                .disambiguation
+                .ask(&game_state.world, name, candidates)
+        }
+    };
+    ActionResult::failure(message)
 }
 
-fn find_usable_entity_in_room(game_state: &GameState, cont_name: &str) -> Option<EntityId> {
-    let search_name = cont_name.to_lowercase();
+/// Runs `action` for every entity in `entity_ids` and reports each result on its own line. It
+/// only fails if it didn't work for any of them.
+fn for_each(
+    game_state: &mut GameState,
+    entity_ids: Vec<EntityId>,
+    nothing: &str,
+    mut action: impl FnMut(&mut GameState, EntityId) -> Result<String, String>,
+) -> ActionResult {
+    if entity_ids.is_empty() {
+        return ActionResult::failure(nothing);
+    }
 
-    if let Some(room_entity_ids) = game_state.current_room_entities() {
-        for entity_id in room_entity_ids {
-            if let Some(entity) = game_state.world.entities.get(entity_id) {
-                //eprintln!("DEBUG: Comparing: '{}' with '{}'", entity.name().to_lowercase(), search_name);
-                if entity.name().to_lowercase() == search_name {
-                    if entity.as_usable().is_some() {
-                        //eprintln!("It's usable!");
-                        return Some(*entity_id);
-                    } else {
-                        //eprintln!("It's not usable!");
-                    }
-                }
-                for alt_name in entity.aliases() {
-                    //eprintln!("DEBUG: Comparing: '{}' with '{}'", alt_name, search_name);
-                    if *alt_name == search_name {
-                        if entity.as_usable().is_some() {
-                            //eprintln!("It's usable!");
-                            return Some(*entity_id);
-                        } else {
-                            //eprintln!("It's not usable!");
-                        }
-                    }
-                }
-            }
-        }
+    game_state.mentioned = entity_ids.clone();
+    let results: Vec<Result<String, String>> = entity_ids
+        .into_iter()
+        .map(|entity_id| action(game_state, entity_id))
+        .collect();
+    let failed = results.iter().all(Result::is_err);
+    let messages: Vec<String> = results
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|message| message))
+        .collect();
+    ActionResult {
+        failed,
+        ..ActionResult::message(messages.join("\n"))
     }
-    None
+}
+
+fn entity_name(game_state: &GameState, entity_id: EntityId) -> String {
+    game_state
+        .world
+        .entities
+        .get(&entity_id)
+        .map(|entity| entity.name().to_lowercase())
+        .unwrap_or_else(|| entity_id.to_string())
 }
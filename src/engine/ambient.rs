@@ -0,0 +1,85 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::engine::state::GameState;
+use crate::engine::vitals::DRAINING;
+use crate::world::event::Effect;
+
+/// Things that happen in real time rather than in turns. The frontend decides when they are
+/// due; the game decides what, if anything, the player notices.
+#[derive(Clone, Copy, Debug)]
+pub enum AmbientEvent {
+    /// The shuttle making itself heard.
+    Ambience,
+    /// The player hasn't typed anything for a while.
+    Idle,
+    /// Time to repeat whatever alarm is going off.
+    Alarm,
+}
+
+const AMBIENCE: &[&str] = &[
+    "The hull creaks somewhere behind the wall.",
+    "The lights flicker for a moment.",
+    "A ventilation fan whirs up, rattles, and dies down again.",
+    "Something metallic rolls across the floor a deck below.",
+    "The air recycler sighs.",
+];
+
+const MUTTERINGS: &[&str] = &[
+    "\"Come on, think,\" you mutter to yourself.",
+    "You hum a tune you can't quite place.",
+    "\"Hello? Anybody?\" Nobody answers.",
+    "You drum your fingers on your leg.",
+];
+
+/// What the player notices of `event`, if anything.
+pub fn describe(game_state: &mut GameState, event: AmbientEvent) -> Option<String> {
+    if game_state.outcome.is_some() {
+        return None;
+    }
+
+    match event {
+        // Not every creak is worth telling about.
+        AmbientEvent::Ambience => game_state
+            .ambient_rng
+            .gen_bool(0.5)
+            .then(|| pick(game_state, AMBIENCE)),
+        AmbientEvent::Idle => Some(muttering(game_state)),
+        AmbientEvent::Alarm => active_alarm(game_state).map(str::to_string),
+    }
+}
+
+fn muttering(game_state: &mut GameState) -> String {
+    let vitals = &game_state.vitals;
+    if vitals.thirst >= DRAINING {
+        "\"Water... I should find some water,\" you mutter.".to_string()
+    } else if vitals.hunger >= DRAINING {
+        "Your stomach grumbles into the silence.".to_string()
+    } else {
+        pick(game_state, MUTTERINGS)
+    }
+}
+
+fn pick(game_state: &mut GameState, messages: &[&str]) -> String {
+    messages
+        .choose(&mut game_state.ambient_rng)
+        .map_or(String::new(), |message| message.to_string())
+}
+
+/// The alarm set off by a scheduled event that has already happened, unless a later one
+/// silenced it.
+pub fn active_alarm(game_state: &GameState) -> Option<&str> {
+    game_state
+        .world
+        .events
+        .iter()
+        .filter(|event| event.at <= game_state.turn)
+        .flat_map(|event| event.effects.iter().map(move |effect| (event.at, effect)))
+        .filter_map(|(at, effect)| match effect {
+            Effect::Alarm { message } => Some((at, Some(message.as_str()))),
+            Effect::SilenceAlarm => Some((at, None)),
+            _ => None,
+        })
+        .max_by_key(|(at, _)| *at)
+        .and_then(|(_, message)| message)
+}
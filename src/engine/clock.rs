@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use crate::engine::actions;
+use crate::engine::eva;
+use crate::engine::outcome::ActionResult;
+use crate::engine::shuttle::ShuttleState;
+use crate::engine::state::GameState;
+use crate::engine::vitals::Vitals;
+use crate::entity::item::card::Card;
+use crate::entity::item::ItemId;
+use crate::entity::{Entity, EntityId};
+use crate::world::event::Effect;
+use crate::world::room::{RoomAttributes, RoomIdentifier};
+
+/// Lets one turn pass: the player's body wears on, and the events scheduled for the new turn
+/// happen.
+pub fn pass_turn(game_state: &mut GameState) -> ActionResult {
+    game_state.turn += 1;
+
+    let breathable = actions::is_breathable(game_state);
+    let burdened = actions::is_burdened(game_state);
+    let mut result = game_state.vitals.tick(breathable, burdened);
+    result.messages.extend(eva::breathe(game_state).messages);
+    if result.outcome.is_none() {
+        let drifted = eva::drift(game_state);
+        result.messages.extend(drifted.messages);
+        result.outcome = drifted.outcome;
+    }
+    if result.outcome.is_none() {
+        let flown = game_state.fly_shuttle();
+        result.messages.extend(flown.messages);
+        result.outcome = flown.outcome;
+    }
+
+    let turn = game_state.turn;
+    let world = &mut game_state.world;
+    for event in world.events.iter().filter(|event| event.at == turn) {
+        if result.outcome.is_some() {
+            break;
+        }
+        result.push(event.message.clone());
+        for effect in &event.effects {
+            let changed = apply(
+                &mut world.rooms,
+                &mut world.entities,
+                &mut game_state.vitals,
+                &mut game_state.shuttle_state,
+                effect,
+            );
+            result.messages.extend(changed.messages);
+            result.outcome = result.outcome.or(changed.outcome);
+        }
+    }
+
+    result
+}
+
+/// Waits `turns` turns, or until something happens that the player should react to.
+pub fn wait(game_state: &mut GameState, turns: Option<&str>) -> ActionResult {
+    let turns = match turns.map(str::parse::<u32>) {
+        None => 1,
+        Some(Ok(turns)) if turns > 0 => turns,
+        _ => return "Wait for how many turns?".into(),
+    };
+
+    let mut waited = 0;
+    let mut happened = ActionResult::new();
+    while waited < turns && happened.messages.is_empty() && happened.outcome.is_none() {
+        happened = pass_turn(game_state);
+        waited += 1;
+    }
+
+    let mut result = match waited {
+        1 => ActionResult::message("Time passes."),
+        _ if waited < turns => ActionResult::message(format!(
+            "You wait for {} turns, until something makes you stop.",
+            waited
+        )),
+        _ => ActionResult::message(format!("You wait for {} turns.", waited)),
+    };
+    result.messages.extend(happened.messages);
+    result.outcome = happened.outcome;
+    result
+}
+
+/// Puts back what the scheduled events did to room descriptions, which saves don't record.
+pub fn replay_descriptions(game_state: &mut GameState) {
+    let turn = game_state.turn;
+    for event in game_state
+        .world
+        .events
+        .iter()
+        .filter(|event| event.at <= turn)
+    {
+        for effect in &event.effects {
+            if let Effect::Describe {
+                room,
+                short_description,
+            } = effect
+            {
+                if let Some(room) = game_state.world.rooms.get_mut(room) {
+                    room.short_description.clone_from(short_description);
+                }
+            }
+        }
+    }
+}
+
+fn apply(
+    rooms: &mut HashMap<RoomIdentifier, RoomAttributes>,
+    entities: &mut HashMap<EntityId, Box<dyn Entity>>,
+    vitals: &mut Vitals,
+    shuttle_state: &mut ShuttleState,
+    effect: &Effect,
+) -> ActionResult {
+    match effect {
+        Effect::Describe {
+            room,
+            short_description,
+        } => {
+            if let Some(room) = rooms.get_mut(room) {
+                room.short_description.clone_from(short_description);
+            }
+        }
+        Effect::Connect {
+            room,
+            direction,
+            passage,
+            to,
+        } => {
+            if let Some(room) = rooms.get_mut(room) {
+                room.connected_rooms.retain(|(d, _, _)| d != direction);
+                room.connected_rooms.push((*direction, *passage, *to));
+            }
+        }
+        Effect::Disconnect { room, direction } => {
+            if let Some(room) = rooms.get_mut(room) {
+                room.connected_rooms.retain(|(d, _, _)| d != direction);
+            }
+        }
+        Effect::Vitals {
+            hunger,
+            thirst,
+            oxygen,
+            fatigue,
+        } => {
+            return vitals.adjust(*hunger, *thirst, *oxygen, *fatigue);
+        }
+        Effect::Drift { x, y, speed } => shuttle_state.push(*x, *y, *speed),
+        Effect::RevokeCard { card } => {
+            if let Some(card) = card_mut(entities, *card) {
+                card.revoke();
+            }
+        }
+        Effect::DamageCard { card } => {
+            if let Some(card) = card_mut(entities, *card) {
+                card.damage();
+            }
+        }
+        Effect::CloneCard { card, onto } => {
+            if let Some(access) = card_mut(entities, *card).map(|card| card.access()) {
+                if let Some(onto) = card_mut(entities, *onto) {
+                    onto.copy_credentials(access);
+                }
+            }
+        }
+        // The frontend repeats alarms, see `ambient::active_alarm`
+        Effect::Alarm { .. } | Effect::SilenceAlarm => {}
+    }
+    ActionResult::new()
+}
+
+fn card_mut(entities: &mut HashMap<EntityId, Box<dyn Entity>>, id: ItemId) -> Option<&mut Card> {
+    entities
+        .get_mut(&EntityId::Item(id))
+        .and_then(|entity| Card::as_card_mut(entity.as_mut()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::state::GameState;
+    use crate::entity::furniture::FurnId;
+    use crate::entity::EntityId;
+    use crate::world::room::RoomIdentifier;
+
+    #[test]
+    fn the_hull_shudders_before_a_player_who_never_eats_starves() {
+        let mut game_state = GameState::with_seed(RoomIdentifier::Storage, 39).unwrap();
+        // Look around the shuttle, find the bosun's card and read up on the terminal, but
+        // never stop for a meal.
+        for command in [
+            "look",
+            "take bucket",
+            "e",
+            "look",
+            "s",
+            "look",
+            "e",
+            "n",
+            "open desk",
+            "take card from desk",
+            "take slip from desk",
+            "read slip",
+            "s",
+            "w",
+            "n",
+            "w",
+            "drop bucket",
+            "e",
+            "s",
+            "s",
+            "look",
+            "n",
+        ] {
+            crate::process_line(&mut game_state, command);
+        }
+        // Waiting stops at every warning, so keep waiting until something else happens.
+        let mut waited = String::new();
+        while !waited.contains("orbit is decaying") && game_state.outcome.is_none() {
+            waited = crate::process_line(&mut game_state, "wait 100").to_string();
+        }
+
+        assert_eq!(game_state.outcome, None, "died on turn {}", game_state.turn);
+        assert!(game_state.vitals.hunger < 90);
+    }
+
+    #[test]
+    fn the_decaying_orbit_pulls_the_shuttle_away() {
+        let mut game_state = GameState::with_seed(RoomIdentifier::Storage, 39).unwrap();
+        while game_state.turn < 45 {
+            super::pass_turn(&mut game_state);
+        }
+
+        let terminal = EntityId::Furniture(FurnId::MainTerminal);
+        let status = game_state
+            .enter_shuttle_command(terminal, "02::09::00")
+            .to_string();
+        assert!(status.contains("closing at -4 m/turn"), "{status}");
+    }
+}
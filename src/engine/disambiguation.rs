@@ -0,0 +1,189 @@
+use std::mem;
+
+use crate::entity::EntityId;
+use crate::parser::command::Command;
+use crate::world::data::World;
+
+const PRONOUNS: [&str; 3] = ["it", "them", "that"];
+
+pub fn is_pronoun(name: &str) -> bool {
+    PRONOUNS.contains(&name)
+}
+
+struct Question {
+    name: String,
+    candidates: Vec<EntityId>,
+}
+
+struct Pending {
+    command: Command,
+    question: Question,
+    choices: Vec<(String, EntityId)>,
+}
+
+/// Keeps track of "which card do you mean?" questions. A command that hits an ambiguous name
+/// is parked until the player answers, then it runs again with the answer applied.
+#[derive(Default)]
+pub struct Disambiguation {
+    pending: Option<Pending>,
+    question: Option<Question>,
+    choices: Vec<(String, EntityId)>,
+}
+
+impl Disambiguation {
+    /// Records that `name` matched all of `candidates` and returns the question for the player.
+    pub fn ask(&mut self, world: &World, name: &str, candidates: Vec<EntityId>) -> String {
+        let names: Vec<String> = candidates
+            .iter()
+            .filter_map(|id| world.entities.get(id))
+            .map(|entity| format!("the {}", entity.name().to_lowercase()))
+            .collect();
+        let options = match names.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+            None => String::new(),
+        };
+
+        self.question = Some(Question {
+            name: name.to_string(),
+            candidates,
+        });
+        let noun = if is_pronoun(name) { "one" } else { name };
+        format!("Which {} do you mean: {}?", noun, options)
+    }
+
+    /// The entity the player already picked for `name`, if it's one of `candidates`.
+    pub fn choice(&self, name: &str, candidates: &[EntityId]) -> Option<EntityId> {
+        self.choices
+            .iter()
+            .find(|(chosen_name, id)| chosen_name == name && candidates.contains(id))
+            .map(|(_, id)| *id)
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Takes `input` as the answer to the pending question. Returns the parked command if the
+    /// answer picks exactly one candidate; otherwise the question is dropped.
+    pub fn answer(&mut self, world: &World, input: &str) -> Option<Command> {
+        let pending = self.pending.take()?;
+        let answer = normalize(input);
+
+        let picked: Vec<EntityId> = pending
+            .question
+            .candidates
+            .iter()
+            .copied()
+            .filter(|id| {
+                world
+                    .entities
+                    .get(id)
+                    .is_some_and(|entity| answers(&answer, entity.name(), entity.aliases()))
+            })
+            .collect();
+
+        match picked.as_slice() {
+            [id] => {
+                self.choices = pending.choices;
+                self.choices.push((pending.question.name, *id));
+                Some(pending.command)
+            }
+            _ => None,
+        }
+    }
+
+    /// Called once a command has run. Parks it if it asked a question.
+    pub fn finish(&mut self, command: Option<Command>) {
+        let choices = mem::take(&mut self.choices);
+        if let (Some(question), Some(command)) = (self.question.take(), command) {
+            self.pending = Some(Pending {
+                command,
+                question,
+                choices,
+            });
+        }
+    }
+}
+
+fn normalize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split_whitespace()
+        .filter(|word| !matches!(*word, "the" | "a" | "an" | "one"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// "bosun", "bosun card" and "bosun's card" all answer for the "Bosun Card".
+fn answers(answer: &[String], name: &str, aliases: &[String]) -> bool {
+    if answer.is_empty() {
+        return false;
+    }
+    let name_words = normalize(name);
+    answer == name_words.as_slice()
+        || aliases
+            .iter()
+            .any(|alias| answer == normalize(alias).as_slice())
+        || answer.iter().all(|word| {
+            let word = word.trim_end_matches("'s");
+            name_words
+                .iter()
+                .any(|name_word| name_word.trim_end_matches("'s") == word)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::state::GameState;
+    use crate::entity::item::ItemId;
+    use crate::world::room::RoomIdentifier;
+
+    /// In the bosun's room with the assistant's and the bosun's card in hand.
+    fn two_cards() -> GameState {
+        let mut game_state = GameState::with_seed(RoomIdentifier::Storage, 39).unwrap();
+        for command in ["e", "s", "e", "n", "open desk", "take card from desk"] {
+            crate::process_line(&mut game_state, command);
+        }
+        assert_eq!(game_state.inventory.len(), 2);
+        game_state
+    }
+
+    fn say(game_state: &mut GameState, input: &str) -> String {
+        crate::process_line(game_state, input).to_string()
+    }
+
+    #[test]
+    fn asks_which_one_when_a_name_is_ambiguous() {
+        let mut game_state = two_cards();
+        assert_eq!(
+            say(&mut game_state, "drop card"),
+            "Which card do you mean: the assistant card or the bosun card?"
+        );
+        assert!(game_state.disambiguation.is_pending());
+        assert_eq!(game_state.inventory.len(), 2);
+    }
+
+    #[test]
+    fn runs_the_command_again_with_the_answer() {
+        let mut game_state = two_cards();
+        say(&mut game_state, "drop card");
+
+        assert_eq!(
+            say(&mut game_state, "bosun's"),
+            "You've dropped the bosun card."
+        );
+        assert_eq!(game_state.inventory, [ItemId::AssistantCard]);
+        assert!(!game_state.disambiguation.is_pending());
+    }
+
+    #[test]
+    fn drops_the_question_when_the_answer_matches_nothing() {
+        let mut game_state = two_cards();
+        say(&mut game_state, "drop card");
+        say(&mut game_state, "captain");
+
+        assert!(!game_state.disambiguation.is_pending());
+        assert_eq!(game_state.inventory.len(), 2);
+        assert!(say(&mut game_state, "drop card").starts_with("Which card"));
+    }
+}
@@ -0,0 +1,331 @@
+use serde::{Deserialize, Serialize};
+
+use crate::engine::actions;
+use crate::engine::outcome::{ActionResult, GameOutcome};
+use crate::engine::state::GameState;
+use crate::entity::item::Wearable;
+use crate::entity::EntityId;
+use crate::world::data::World;
+use crate::world::room::{Direction, Outside, RoomIdentifier};
+
+/// How far out from the handrail by the hatch the tether reaches.
+const TETHER_LENGTH: u8 = 2;
+/// The fastest the player can drift into something and still catch hold of it.
+const SAFE_SPEED: i8 = 1;
+const MAX_SPEED: i8 = 4;
+/// How far the player can drift away with nothing to catch before the shuttle is out of reach.
+const OUT_OF_REACH: u8 = 10;
+/// Turns of suit air one burst of the thrusters costs.
+const BURST_AIR: u8 = 2;
+
+// Warnings given once the air left in the suit's tank drops to the level, worst last.
+const SUIT_AIR_WARNINGS: &[(u8, &str)] = &[
+    (5, "A chime sounds in your helmet: SUIT AIR LOW."),
+    (2, "The chime turns into a steady beep: SUIT AIR CRITICAL."),
+    (
+        0,
+        "The beeping stops, and so does the hiss of fresh air in your helmet.",
+    ),
+];
+
+/// Where the player is on a walk outside the shuttle, counted in lengths out from the hatch.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Spacewalk {
+    distance: u8,
+    /// Lengths per turn, away from the hatch when positive.
+    velocity: i8,
+    /// Holding on to the handrail by the hatch.
+    holding: bool,
+    /// The suit's tether is clipped to that handrail.
+    tethered: bool,
+}
+
+impl Spacewalk {
+    /// A spacewalk starts at the hatch of `room`, if `room` is outside the shuttle.
+    pub fn start(world: &World, room: RoomIdentifier) -> Option<Spacewalk> {
+        world.outside(&room).map(|_| Spacewalk {
+            distance: 0,
+            velocity: 0,
+            holding: true,
+            tethered: false,
+        })
+    }
+}
+
+fn outside(game_state: &GameState) -> Option<Outside> {
+    game_state.world.outside(&game_state.current_room)
+}
+
+pub fn is_open_space(game_state: &GameState) -> bool {
+    outside(game_state).is_some()
+}
+
+/// How the player is doing out there, for `look`.
+pub fn describe(game_state: &GameState) -> Option<String> {
+    let walk = game_state.spacewalk.as_ref()?;
+    let outside = outside(game_state)?;
+    if walk.holding {
+        let mut description = "You hold on to the handrail by the hatch.".to_string();
+        if walk.tethered {
+            description.push_str(" Your tether is clipped to it.");
+        }
+        return Some(description);
+    }
+
+    let mut description = format!(
+        "You float {} out from the hatch, {}.",
+        lengths(walk.distance),
+        motion(walk.velocity)
+    );
+    if let Some((_, at)) = outside.reach {
+        description.push_str(&format!(
+            " The station's airlock is {} away.",
+            lengths(at.saturating_sub(walk.distance))
+        ));
+    }
+    if walk.tethered {
+        description.push_str(" Your tether trails back to the hatch.");
+    }
+    Some(description)
+}
+
+fn lengths(count: u8) -> String {
+    match count {
+        1 => "one length".to_string(),
+        _ => format!("{} lengths", count),
+    }
+}
+
+fn motion(velocity: i8) -> &'static str {
+    match velocity {
+        0 => "hanging still",
+        1 => "drifting away from the shuttle",
+        v if v > 1 => "speeding away from the shuttle",
+        -1 => "drifting back toward the shuttle",
+        _ => "speeding back toward the shuttle",
+    }
+}
+
+/// Walking means pushing off from the hull out here. Leaves walking back through the hatch,
+/// and everywhere inside the shuttle, to the usual rules.
+pub fn push_off(game_state: &mut GameState, direction: Direction) -> Option<ActionResult> {
+    let outside = outside(game_state)?;
+    let walk = game_state.spacewalk.as_mut()?;
+    if !walk.holding {
+        return Some(ActionResult::failure(
+            "There's nothing to push against out here. Your suit's thrusters could help.",
+        ));
+    }
+    if direction == outside.back {
+        return None;
+    }
+    if direction != outside.away {
+        return Some(ActionResult::failure(
+            "There's only the hull and the stars that way.",
+        ));
+    }
+    walk.holding = false;
+    walk.velocity = 1;
+    Some("You tuck your legs and push off from the hull. The handrail slips out of reach.".into())
+}
+
+pub fn thrust(game_state: &mut GameState, direction: Direction) -> ActionResult {
+    let Some(outside) = outside(game_state) else {
+        return ActionResult::failure("Your suit's thrusters are for open space, not for in here.");
+    };
+    let change = if direction == outside.away {
+        1
+    } else if direction == outside.back {
+        -1
+    } else {
+        return ActionResult::failure("Firing that way would only send you tumbling off course.");
+    };
+    if tank(game_state).map_or(0, |tank| tank.air()) < BURST_AIR {
+        return ActionResult::failure(
+            "The thrusters sputter. There isn't enough air left in the tank to fire them.",
+        );
+    }
+
+    let mut result = ActionResult::new();
+    let Some(walk) = game_state.spacewalk.as_mut() else {
+        return ActionResult::failure("Your suit's thrusters are for open space, not for in here.");
+    };
+    let let_go = if walk.holding {
+        "You let go of the handrail. "
+    } else {
+        ""
+    };
+    walk.holding = false;
+    walk.velocity = (walk.velocity + change).clamp(-MAX_SPEED, MAX_SPEED);
+    let way = if change > 0 {
+        "away from the hull"
+    } else {
+        "back toward the hull"
+    };
+    result.push(format!(
+        "{}A burst from your thruster pack pushes you {}. You're {} now.",
+        let_go,
+        way,
+        motion(walk.velocity)
+    ));
+    result.messages.extend(draw_air(game_state, BURST_AIR));
+    result
+}
+
+pub fn clip(game_state: &mut GameState, obj_name: Option<&str>) -> ActionResult {
+    if obj_name.is_some_and(|name| !is_tether(name)) {
+        return ActionResult::failure("You can only clip your suit's tether to something.");
+    }
+    let Some(walk) = game_state.spacewalk.as_mut() else {
+        return ActionResult::failure("There's nothing to clip your tether to here.");
+    };
+    if walk.tethered {
+        return ActionResult::failure("Your tether is already clipped to the handrail.");
+    }
+    if !walk.holding {
+        return ActionResult::failure("The handrail by the hatch is out of reach.");
+    }
+    walk.tethered = true;
+    "You clip your tether to the handrail by the hatch. The line is only a couple of lengths long."
+        .into()
+}
+
+pub fn unclip(game_state: &mut GameState, obj_name: Option<&str>) -> ActionResult {
+    if obj_name.is_some_and(|name| !is_tether(name)) {
+        return ActionResult::failure("You can only unclip your suit's tether.");
+    }
+    match game_state.spacewalk.as_mut() {
+        Some(walk) if walk.tethered => {
+            walk.tethered = false;
+            "You unclip the tether, and it reels back into its coil at your hip.".into()
+        }
+        _ => ActionResult::failure("Your tether isn't clipped to anything."),
+    }
+}
+
+/// Hauls the player back to the hatch along the tether.
+pub fn pull(game_state: &mut GameState, obj_name: &str) -> ActionResult {
+    let walk = match game_state.spacewalk.as_mut() {
+        Some(walk) if walk.tethered && is_tether(obj_name) => walk,
+        _ => {
+            return ActionResult::failure(format!(
+                "Pulling the {} doesn't get you anywhere.",
+                obj_name
+            ))
+        }
+    };
+    if walk.holding {
+        return ActionResult::failure("The tether hangs slack. You're already at the hatch.");
+    }
+    walk.distance = 0;
+    walk.velocity = 0;
+    walk.holding = true;
+    "You haul yourself back along the tether, hand over hand, and catch the handrail by the hatch."
+        .into()
+}
+
+fn is_tether(name: &str) -> bool {
+    ["tether", "line", "handrail", "rail"]
+        .iter()
+        .any(|word| name.contains(word))
+}
+
+/// Lets the player drift for a turn, and catches them on whatever they drift into.
+pub fn drift(game_state: &mut GameState) -> ActionResult {
+    let Some(outside) = outside(game_state) else {
+        return ActionResult::new();
+    };
+    let Some(walk) = game_state.spacewalk.as_mut() else {
+        return ActionResult::new();
+    };
+    if walk.holding {
+        return ActionResult::new();
+    }
+
+    let next = i16::from(walk.distance) + i16::from(walk.velocity);
+    let speed = walk.velocity.abs();
+    if walk.tethered && next > i16::from(TETHER_LENGTH) {
+        walk.distance = TETHER_LENGTH;
+        walk.velocity = 0;
+        return "The tether snaps taut and jerks you to a stop.".into();
+    }
+    if next <= 0 {
+        if speed > SAFE_SPEED && !walk.tethered {
+            return lost("You hit the hull too fast to catch the handrail, and bounce off, spinning, into the dark.");
+        }
+        walk.distance = 0;
+        walk.velocity = 0;
+        walk.holding = true;
+        return "You bump against the hull and catch the handrail by the hatch.".into();
+    }
+    match outside.reach {
+        Some((room, at)) if next >= i16::from(at) => {
+            if speed > SAFE_SPEED {
+                return lost("You slam into the station's hull far too fast to catch hold of anything, and bounce off into the dark.");
+            }
+            let result = ActionResult::message(
+                "The station's hull drifts up to meet you, and you catch a handrail by its airlock.",
+            );
+            actions::enter_room(game_state, room, result)
+        }
+        None if next >= i16::from(OUT_OF_REACH) => lost("The shuttle shrinks behind you until it's just another light among the stars. There's nothing out here to catch hold of."),
+        _ => {
+            walk.distance = next as u8;
+            ActionResult::new()
+        }
+    }
+}
+
+fn lost(message: &str) -> ActionResult {
+    ActionResult::message(message).with_outcome(GameOutcome::LostInSpace)
+}
+
+/// In vacuum the player breathes from the tank of their suit, a turn's worth at a time.
+pub fn breathe(game_state: &mut GameState) -> ActionResult {
+    let mut result = ActionResult::new();
+    if actions::is_in_vacuum(game_state) {
+        result.messages.extend(draw_air(game_state, 1));
+    }
+    result
+}
+
+/// Takes `amount` turns of air from the suit's tank, and warns as it runs low.
+fn draw_air(game_state: &mut GameState, amount: u8) -> Vec<String> {
+    let Some(tank) = tank_mut(game_state) else {
+        return Vec::new();
+    };
+    let before = tank.air();
+    let after = before.saturating_sub(amount);
+    tank.set_air(after);
+    SUIT_AIR_WARNINGS
+        .iter()
+        .filter(|(level, _)| before > *level && after <= *level)
+        .map(|(_, warning)| warning.to_string())
+        .collect()
+}
+
+/// The worn gear with an air tank.
+pub fn tank(game_state: &GameState) -> Option<&dyn Wearable> {
+    game_state
+        .worn
+        .iter()
+        .filter_map(|item_id| game_state.world.entities.get(&EntityId::Item(*item_id)))
+        .filter_map(|entity| entity.as_wearable())
+        .find(|gear| gear.tank() > 0)
+}
+
+fn tank_mut(game_state: &mut GameState) -> Option<&mut dyn Wearable> {
+    let tank_id = game_state.worn.iter().copied().find(|item_id| {
+        game_state
+            .world
+            .entities
+            .get(&EntityId::Item(*item_id))
+            .and_then(|entity| entity.as_wearable())
+            .is_some_and(|gear| gear.tank() > 0)
+    })?;
+    game_state
+        .world
+        .entities
+        .get_mut(&EntityId::Item(tank_id))
+        .and_then(|entity| entity.as_wearable_mut())
+}
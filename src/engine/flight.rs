@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+
+use crate::engine::outcome::{ActionResult, GameOutcome};
+
+type Vector = [f32; 3];
+
+/// Where the station is from where the shuttle starts: bearing X and Y in degrees, and
+/// distance in meters.
+const STATION_BEARING: (f32, f32) = (345.0, 0.0);
+const STATION_DISTANCE: f32 = 200.0;
+/// How close the shuttle has to pass the docking port for the clamps to reach it.
+const PORT_RADIUS: f32 = 25.0;
+/// How close the shuttle can pass the docking port without hitting the station around it.
+const HULL_RADIUS: f32 = 50.0;
+/// The fastest the shuttle can come in, in meters per turn, and still dock instead of crash.
+const DOCKING_SPEED: f32 = 50.0;
+/// How many degrees the shuttle's nose can point away from the way it comes in for the
+/// docking port on it to still line up.
+const ALIGNMENT_TOLERANCE: f32 = 10.0;
+/// How far from the station the shuttle can drift before there's no finding it again.
+const LOST_DISTANCE: f32 = 2000.0;
+
+/// The shuttle's flight relative to where it started, stepped forward a turn at a time.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Flight {
+    position: Vector,
+    /// Meters per turn.
+    velocity: Vector,
+    /// Bearing X and Y the shuttle's nose points at. The docking port is on the nose. Missing
+    /// from saves before version 16, which point it at 0, 0.
+    #[serde(default)]
+    heading: (i16, i16),
+    docked: bool,
+}
+
+impl Default for Flight {
+    fn default() -> Self {
+        Flight {
+            position: [0.0; 3],
+            velocity: [0.0; 3],
+            heading: (0, 0),
+            docked: false,
+        }
+    }
+}
+
+impl Flight {
+    pub fn is_docked(&self) -> bool {
+        self.docked
+    }
+
+    /// Changes the velocity by `delta_v` meters per turn in the direction of bearing `x`, `y`.
+    /// The engines push along the shuttle, so it turns to face that way first.
+    pub fn burn(&mut self, x: i16, y: i16, delta_v: f32) {
+        self.turn_to(x, y);
+        self.accelerate(x, y, delta_v);
+    }
+
+    /// Pushes the shuttle like a burn does, but leaves its nose where it points. Nothing moves
+    /// it once it's docked.
+    pub fn push(&mut self, x: i16, y: i16, delta_v: f32) {
+        if !self.docked {
+            self.accelerate(x, y, delta_v);
+        }
+    }
+
+    fn accelerate(&mut self, x: i16, y: i16, delta_v: f32) {
+        let impulse = scale(direction(f32::from(x), f32::from(y)), delta_v);
+        self.velocity = add(self.velocity, impulse);
+        // A burn that cancels the drift leaves rounding errors, not a crawl.
+        if self.speed() < 0.5 {
+            self.velocity = [0.0; 3];
+        }
+    }
+
+    pub fn speed(&self) -> f32 {
+        length(self.velocity)
+    }
+
+    /// Turns the shuttle's nose to bearing `x`, `y` without changing where it drifts.
+    pub fn turn_to(&mut self, x: i16, y: i16) {
+        self.heading = (x.rem_euclid(360), y.rem_euclid(360));
+    }
+
+    pub fn heading(&self) -> (i16, i16) {
+        self.heading
+    }
+
+    /// Bearing X and Y of the way the shuttle drifts, in whole degrees.
+    pub fn drift(&self) -> (i16, i16) {
+        bearing(self.velocity)
+    }
+
+    /// Bearing X and Y of the station from the shuttle, and how far it is.
+    pub fn station(&self) -> ((i16, i16), f32) {
+        let to_station = sub(station(), self.position);
+        (bearing(to_station), length(to_station))
+    }
+
+    /// How fast the shuttle closes in on the station, negative when it's drifting away.
+    pub fn closing_speed(&self) -> f32 {
+        let to_station = sub(station(), self.position);
+        match length(to_station) {
+            // Adding 0 turns -0 into 0 for the readouts.
+            distance if distance > 0.0 => (dot(self.velocity, to_station) / distance).round() + 0.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Lets a turn pass. The shuttle docks if it comes in at the port slowly enough and nose
+    /// first, bounces off if it hits the station beside the port or sideways, and is crushed
+    /// if it comes in too fast.
+    pub fn step(&mut self) -> ActionResult {
+        if self.docked || self.velocity == [0.0; 3] {
+            return ActionResult::new();
+        }
+
+        let start = self.position;
+        self.position = add(start, self.velocity);
+
+        let station = station();
+        let path = self.velocity;
+        let to_station = sub(station, start);
+        let along = dot(to_station, path) / dot(path, path);
+        let closest = add(start, scale(path, along.clamp(0.0, 1.0)));
+        // How far from the port the shuttle would pass if nothing were in the way.
+        let miss = length(sub(station, add(start, scale(path, along))));
+        if along > 0.0 && length(sub(station, closest)) <= HULL_RADIUS {
+            if self.speed() > DOCKING_SPEED {
+                return ActionResult::message("As if in slow motion, the shuttle floats towards the station. Your hope that the approach vector is correct is quickly crushed - just as the shuttle got crushed against the station.
+You lost.").with_outcome(GameOutcome::Died(
+                    "the shuttle was crushed against the station".to_string(),
+                ));
+            }
+            if miss > PORT_RADIUS {
+                self.position = closest;
+                self.velocity = scale(path, -0.5);
+                return "The shuttle bumps into the station at an angle, and the docking clamps close on nothing. It bounces off and slowly drifts back the way it came.".into();
+            }
+            let nose = direction(f32::from(self.heading.0), f32::from(self.heading.1));
+            if angle(nose, path) > ALIGNMENT_TOLERANCE {
+                self.position = closest;
+                self.velocity = scale(path, -0.5);
+                return "The shuttle drifts up to the port with its nose pointing the wrong way, and the docking clamps can't get a grip. It bounces off and slowly drifts back the way it came.".into();
+            }
+            self.position = closest;
+            self.velocity = [0.0; 3];
+            self.docked = true;
+            return "You have successfully maneuvered the shuttle for docking. Congratulations, you've docked at the station! Now let's see what awaits for you next...".into();
+        }
+
+        if length(sub(station, self.position)) > LOST_DISTANCE {
+            return ActionResult::message("You carefully navigate the shuttle... away from the station. Oh no! You have no idea where the station went, as it's no longer visible in any of the view ports. Now you'll die from cold as the shuttle tumbles through the void, no habitable worlds or trade routes for lightyears around you.
+You lost.").with_outcome(GameOutcome::LostInSpace);
+        }
+        ActionResult::new()
+    }
+}
+
+fn station() -> Vector {
+    scale(
+        direction(STATION_BEARING.0, STATION_BEARING.1),
+        STATION_DISTANCE,
+    )
+}
+
+/// The unit vector for bearing `x` around and `y` up, in degrees.
+fn direction(x: f32, y: f32) -> Vector {
+    let (x, y) = (x.to_radians(), y.to_radians());
+    [y.cos() * x.cos(), y.cos() * x.sin(), y.sin()]
+}
+
+fn bearing(vector: Vector) -> (i16, i16) {
+    let length = length(vector);
+    if length == 0.0 {
+        return (0, 0);
+    }
+    let x = vector[1].atan2(vector[0]).to_degrees();
+    let y = (vector[2] / length).asin().to_degrees();
+    let degrees = |angle: f32| (angle.round() as i16).rem_euclid(360);
+    (degrees(x), degrees(y))
+}
+
+fn add(a: Vector, b: Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Vector, b: Vector) -> Vector {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: Vector, factor: f32) -> Vector {
+    [a[0] * factor, a[1] * factor, a[2] * factor]
+}
+
+fn dot(a: Vector, b: Vector) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn length(a: Vector) -> f32 {
+    dot(a, a).sqrt()
+}
+
+/// The angle between `a` and `b`, in degrees.
+fn angle(a: Vector, b: Vector) -> f32 {
+    (dot(a, b) / (length(a) * length(b)))
+        .clamp(-1.0, 1.0)
+        .acos()
+        .to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Heads straight for the port, nose turned `off` degrees from the way in, and lets
+    /// turns pass until the shuttle gets there.
+    fn approach(off: i16) -> Flight {
+        let mut flight = Flight::default();
+        flight.burn(345, 0, 30.0);
+        flight.turn_to(345 + off, 0);
+        for _ in 0..10 {
+            flight.step();
+        }
+        flight
+    }
+
+    #[test]
+    fn docks_nose_first_within_the_tolerance() {
+        assert!(approach(0).is_docked());
+        assert!(approach(9).is_docked());
+        assert!(approach(-9).is_docked());
+    }
+
+    #[test]
+    fn bounces_off_when_misaligned() {
+        let flight = approach(20);
+        assert!(!flight.is_docked());
+        assert!(flight.closing_speed() < 0.0);
+    }
+
+    #[test]
+    fn a_push_moves_the_shuttle_but_leaves_its_nose() {
+        let mut flight = Flight::default();
+        flight.push(165, 0, 4.0);
+        assert_eq!(flight.heading(), (0, 0));
+        assert_eq!(flight.closing_speed(), -4.0);
+
+        let mut docked = approach(0);
+        docked.push(165, 0, 4.0);
+        assert_eq!(docked.speed(), 0.0);
+    }
+}
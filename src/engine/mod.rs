@@ -1,6 +1,16 @@
 pub mod actions;
+pub mod ambient;
+pub mod clock;
+pub mod disambiguation;
+pub mod eva;
+pub mod flight;
+pub mod outcome;
+pub mod resolver;
+pub mod rng;
+pub mod save;
+pub mod script;
 pub mod shuttle;
 pub mod state;
+pub mod terminal;
 pub mod tui;
-
-struct Engine;
+pub mod vitals;
@@ -0,0 +1,96 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// How a game ended. The engine only reports it; the frontend decides what to do next.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameOutcome {
+    Won,
+    Died(String),
+    LostInSpace,
+}
+
+impl fmt::Display for GameOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameOutcome::Won => write!(f, "Congrats, you won!"),
+            GameOutcome::Died(cause) => write!(f, "You died: {}.", cause),
+            GameOutcome::LostInSpace => write!(f, "You are lost in space."),
+        }
+    }
+}
+
+/// What an action produced: the text to show the player, and whether the game is over.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ActionResult {
+    pub messages: Vec<String>,
+    pub outcome: Option<GameOutcome>,
+    /// Set when the action couldn't be done at all, like taking something that isn't there.
+    /// Nothing happened, so it takes no time.
+    pub failed: bool,
+    /// Set when the player asked to stop playing.
+    pub quit: bool,
+}
+
+impl ActionResult {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn message(message: impl Into<String>) -> Self {
+        ActionResult {
+            messages: vec![message.into()],
+            outcome: None,
+            failed: false,
+            quit: false,
+        }
+    }
+
+    /// An action that couldn't be done, with the reason why.
+    pub fn failure(message: impl Into<String>) -> Self {
+        ActionResult {
+            failed: true,
+            ..ActionResult::message(message)
+        }
+    }
+
+    pub fn push(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        if !message.is_empty() {
+            self.messages.push(message);
+        }
+    }
+
+    pub fn with_outcome(mut self, outcome: GameOutcome) -> Self {
+        self.outcome = Some(outcome);
+        self
+    }
+}
+
+impl From<String> for ActionResult {
+    fn from(message: String) -> Self {
+        ActionResult::message(message)
+    }
+}
+
+/// What a step of an action says when it worked, or why it didn't.
+impl From<Result<String, String>> for ActionResult {
+    fn from(result: Result<String, String>) -> Self {
+        match result {
+            Ok(message) => ActionResult::message(message),
+            Err(message) => ActionResult::failure(message),
+        }
+    }
+}
+
+impl From<&str> for ActionResult {
+    fn from(message: &str) -> Self {
+        ActionResult::message(message)
+    }
+}
+
+impl fmt::Display for ActionResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.messages.join("\n"))
+    }
+}
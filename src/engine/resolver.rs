@@ -0,0 +1,297 @@
+use crate::engine::disambiguation::is_pronoun;
+use crate::engine::state::GameState;
+use crate::entity::furniture::main_terminal::MainTerminal;
+use crate::entity::{Entity, EntityId};
+use crate::parser::suggest;
+
+/// Where to look for an entity the player named.
+#[derive(Clone, Copy)]
+pub enum Scope {
+    Room,
+    Inventory,
+    /// What the player wears.
+    Worn,
+    /// Whatever lies in the containers in the room or in the inventory.
+    Containers,
+    /// Whatever lies in one particular container, unless it's closed.
+    Container(EntityId),
+}
+
+/// Everything the player can see or reach.
+pub const ALL: &[Scope] = &[
+    Scope::Room,
+    Scope::Inventory,
+    Scope::Worn,
+    Scope::Containers,
+];
+/// The room and the inventory, without looking into containers.
+pub const NEARBY: &[Scope] = &[Scope::Room, Scope::Inventory];
+
+/// What the entity has to be good for, so "read card" doesn't stop at a card you can't read.
+#[derive(Clone, Copy)]
+pub enum Capability {
+    Any,
+    Containable,
+    Drinkable,
+    Edible,
+    Openable,
+    Readable,
+    Usable,
+    Wearable,
+}
+
+impl Capability {
+    fn allows(self, entity: &dyn Entity) -> bool {
+        match self {
+            Capability::Any => true,
+            Capability::Containable => entity.as_containable().is_some(),
+            Capability::Drinkable => entity.as_drinkable().is_some(),
+            Capability::Edible => entity.as_edible().is_some(),
+            Capability::Openable => entity.as_openable().is_some(),
+            Capability::Readable => entity.as_readable().is_some(),
+            // Using a terminal logs the player in to it.
+            Capability::Usable => {
+                entity.as_usable().is_some() || MainTerminal::as_terminal(entity).is_some()
+            }
+            Capability::Wearable => entity.as_wearable().is_some(),
+        }
+    }
+}
+
+/// Why a name didn't resolve to exactly one entity.
+pub enum LookupError {
+    /// Nothing is called that. Carries the name of something in scope that's close to it.
+    Missing(Option<String>),
+    Ambiguous(Vec<EntityId>),
+    /// A pronoun stands for something the command can't get at, like "take it" for a thing
+    /// left in another room.
+    OutOfScope(EntityId),
+}
+
+/// Finds the one entity in `scopes` that is called `name` and has `capability`. "it", "them"
+/// and "that" stand for the entities mentioned last, wherever they are. When several match,
+/// the player's answer to an earlier "which one" question decides.
+pub fn resolve(
+    game_state: &mut GameState,
+    name: &str,
+    scopes: &[Scope],
+    capability: Capability,
+) -> Result<EntityId, LookupError> {
+    let name = name.to_lowercase();
+    let in_scope: Vec<EntityId> = entities_in(game_state, scopes)
+        .into_iter()
+        .filter(|id| {
+            game_state
+                .world
+                .entities
+                .get(id)
+                .is_some_and(|entity| capability.allows(entity.as_ref()))
+        })
+        .collect();
+
+    let matches: Vec<EntityId> = if is_pronoun(&name) {
+        let mentioned = &game_state.mentioned;
+        let matches: Vec<EntityId> = mentioned
+            .iter()
+            .copied()
+            .filter(|id| in_scope.contains(id))
+            .collect();
+        match mentioned.last() {
+            Some(entity_id) if matches.is_empty() => {
+                return Err(LookupError::OutOfScope(*entity_id))
+            }
+            _ => matches,
+        }
+    } else {
+        let named = game_state.world.entities_named(&name);
+        in_scope
+            .iter()
+            .copied()
+            .filter(|id| named.contains(id))
+            .collect()
+    };
+
+    let entity_id = match matches.as_slice() {
+        [] if is_pronoun(&name) => return Err(LookupError::Missing(None)),
+        [] => {
+            return Err(LookupError::Missing(closest_name(
+                game_state, &name, &in_scope,
+            )))
+        }
+        [entity_id] => *entity_id,
+        _ => game_state
+            .disambiguation
+            .choice(&name, &matches)
+            .ok_or(LookupError::Ambiguous(matches))?,
+    };
+    game_state.mentioned = vec![entity_id];
+    Ok(entity_id)
+}
+
+/// "all" and "everything" stand for every entity in `scopes`, "them" for the ones mentioned
+/// last. Any other name is not a group, and gives `None`.
+pub fn resolve_group(
+    game_state: &GameState,
+    name: &str,
+    scopes: &[Scope],
+) -> Option<Vec<EntityId>> {
+    let in_scope = entities_in(game_state, scopes);
+    match name {
+        "all" | "everything" => Some(in_scope),
+        "them" => Some(
+            in_scope
+                .into_iter()
+                .filter(|id| game_state.mentioned.contains(id))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// The names and aliases of everything in `scopes`, lowercased.
+pub fn names_in(game_state: &GameState, scopes: &[Scope]) -> Vec<String> {
+    entities_in(game_state, scopes)
+        .iter()
+        .filter_map(|id| game_state.world.entities.get(id))
+        .flat_map(|entity| {
+            std::iter::once(entity.name())
+                .chain(entity.aliases().iter().map(String::as_str))
+                .map(str::to_lowercase)
+        })
+        .collect()
+}
+
+/// The name of the entity among `entity_ids` whose name or alias looks like a typo of `name`.
+fn closest_name(game_state: &GameState, name: &str, entity_ids: &[EntityId]) -> Option<String> {
+    let entities: Vec<&dyn Entity> = entity_ids
+        .iter()
+        .filter_map(|id| game_state.world.entities.get(id))
+        .map(|entity| entity.as_ref())
+        .collect();
+    let names_of = |entity: &dyn Entity| -> Vec<String> {
+        std::iter::once(entity.name())
+            .chain(entity.aliases().iter().map(String::as_str))
+            .map(str::to_lowercase)
+            .collect()
+    };
+
+    let names: Vec<String> = entities
+        .iter()
+        .flat_map(|entity| names_of(*entity))
+        .collect();
+    let best = suggest::closest(name, names.iter().map(String::as_str))?;
+    entities
+        .iter()
+        .find(|entity| {
+            names_of(**entity)
+                .iter()
+                .any(|entity_name| entity_name == best)
+        })
+        .map(|entity| entity.name().to_lowercase())
+}
+
+/// The entities in `scopes`, in the order the scopes are given, without repeats.
+pub fn entities_in(game_state: &GameState, scopes: &[Scope]) -> Vec<EntityId> {
+    let mut entity_ids: Vec<EntityId> = Vec::new();
+    for scope in scopes {
+        for entity_id in scope_entities(game_state, *scope) {
+            if !entity_ids.contains(&entity_id) {
+                entity_ids.push(entity_id);
+            }
+        }
+    }
+    entity_ids
+}
+
+fn scope_entities(game_state: &GameState, scope: Scope) -> Vec<EntityId> {
+    match scope {
+        Scope::Room => {
+            let mut entities = game_state
+                .current_room_entities()
+                .cloned()
+                .unwrap_or_default();
+            entities.extend(game_state.world.doors_in(&game_state.current_room));
+            entities
+        }
+        Scope::Inventory => game_state
+            .inventory
+            .iter()
+            .map(|item_id| EntityId::Item(*item_id))
+            .collect(),
+        Scope::Worn => game_state
+            .worn
+            .iter()
+            .map(|item_id| EntityId::Item(*item_id))
+            .collect(),
+        Scope::Containers => entities_in(game_state, NEARBY)
+            .into_iter()
+            .flat_map(|container_id| scope_entities(game_state, Scope::Container(container_id)))
+            .collect(),
+        Scope::Container(container_id) if game_state.world.is_closed(container_id) => Vec::new(),
+        Scope::Container(container_id) => game_state
+            .world
+            .get_containable(container_id)
+            .map(|container| container.contains().clone())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::state::GameState;
+    use crate::entity::item::ItemId;
+    use crate::world::room::RoomIdentifier;
+
+    fn play(commands: &[&str]) -> (GameState, String) {
+        let mut game_state = GameState::with_seed(RoomIdentifier::Storage, 39).unwrap();
+        let mut last = String::new();
+        for command in commands {
+            last = crate::process_line(&mut game_state, command).to_string();
+        }
+        (game_state, last)
+    }
+
+    #[test]
+    fn it_stands_for_what_take_all_picked_up() {
+        let (game_state, last) = play(&["take all", "drop it"]);
+        assert_eq!(last, "You've dropped the bucket.");
+        assert_eq!(game_state.inventory, [ItemId::AssistantCard]);
+    }
+
+    #[test]
+    fn it_still_means_something_left_in_another_room() {
+        let (_, last) = play(&["look at bucket", "e", "take it"]);
+        assert_eq!(last, "The bucket isn't here.");
+    }
+
+    #[test]
+    fn it_still_means_something_the_command_cant_use() {
+        let (_, last) = play(&["take bucket", "take it"]);
+        assert_eq!(last, "You can't do that with the bucket.");
+    }
+
+    #[test]
+    fn it_means_nothing_before_anything_was_mentioned() {
+        let (_, last) = play(&["take it"]);
+        assert_eq!(last, "You're not sure what \"it\" refers to.");
+    }
+
+    #[test]
+    fn suggests_a_close_name_in_scope() {
+        let (_, last) = play(&["look at buckt"]);
+        assert_eq!(
+            last,
+            "There is no buckt here to look at. Did you mean the bucket?"
+        );
+        let (_, last) = play(&["take bxcxet"]);
+        assert_eq!(last, "There is no bxcxet here. Did you mean the bucket?");
+    }
+
+    #[test]
+    fn suggests_nothing_too_far_off_or_out_of_scope() {
+        let (_, last) = play(&["take bxcxxt"]);
+        assert_eq!(last, "There is no bxcxxt here.");
+        let (_, last) = play(&["take mess tabel"]);
+        assert_eq!(last, "There is no mess tabel here.");
+    }
+}
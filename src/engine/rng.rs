@@ -0,0 +1,130 @@
+use rand::{Error, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// The stream ambience draws from, next to the game's own stream 0.
+const AMBIENCE_STREAM: u64 = 1;
+
+/// The one source of randomness in a game. It is seeded, so the same seed and the same
+/// commands always give the same transcript, and saves record how far into the stream it is.
+pub struct GameRng {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        GameRng {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Picks a seed at random, for games started without `--seed`.
+    pub fn from_entropy() -> Self {
+        GameRng::new(rand::random())
+    }
+
+    /// Continues the stream of `seed` after `word_pos` words were drawn from it.
+    pub fn resume(seed: u64, word_pos: u128) -> Self {
+        let mut rng = GameRng::new(seed);
+        rng.rng.set_word_pos(word_pos);
+        rng
+    }
+
+    /// A stream of its own for the seed, for ambience. Ambient messages come at real-time
+    /// moments, so drawing them from the game's stream would make a seed replay differently
+    /// depending on how fast the player types.
+    pub fn ambience(&self) -> Self {
+        let mut rng = GameRng::new(self.seed);
+        rng.rng.set_stream(AMBIENCE_STREAM);
+        rng
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn word_pos(&self) -> u128 {
+        self.rng.get_word_pos()
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ambient::{self, AmbientEvent};
+    use crate::engine::state::GameState;
+    use crate::world::room::RoomIdentifier;
+
+    // Nonsense gets a random reaction, and so does walking through a door.
+    const COMMANDS: &[&str] = &["look", "xyzzy", "e", "w", "plugh", "e", "s", "frobnicate"];
+
+    fn transcript(seed: u64) -> Vec<String> {
+        transcript_with_ambience(seed, 0)
+    }
+
+    /// Plays `COMMANDS`, with `ambience` ambient events before each.
+    fn transcript_with_ambience(seed: u64, ambience: usize) -> Vec<String> {
+        let mut game_state = GameState::with_seed(RoomIdentifier::Storage, seed).unwrap();
+        COMMANDS
+            .iter()
+            .map(|command| {
+                for _ in 0..ambience {
+                    ambient::describe(&mut game_state, AmbientEvent::Ambience);
+                    ambient::describe(&mut game_state, AmbientEvent::Idle);
+                }
+                crate::process_line(&mut game_state, command).to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_same_seed_replays_the_same_game() {
+        assert_eq!(transcript(39), transcript(39));
+    }
+
+    #[test]
+    fn another_seed_plays_differently() {
+        assert_ne!(transcript(39), transcript(40));
+    }
+
+    #[test]
+    fn ambience_does_not_change_the_game() {
+        assert_eq!(transcript(39), transcript_with_ambience(39, 3));
+    }
+
+    #[test]
+    fn ambience_has_a_stream_of_its_own() {
+        let mut rng = GameRng::new(7);
+        let mut ambience = rng.ambience();
+        assert_ne!(ambience.next_u64(), rng.next_u64());
+        assert_eq!(ambience.seed(), 7);
+    }
+
+    #[test]
+    fn resuming_continues_the_stream() {
+        let mut rng = GameRng::new(7);
+        rng.next_u64();
+        let mut resumed = GameRng::resume(7, rng.word_pos());
+        assert_eq!(resumed.next_u64(), rng.next_u64());
+        assert_eq!(resumed.seed(), 7);
+    }
+}
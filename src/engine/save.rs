@@ -0,0 +1,585 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::clock;
+use crate::engine::eva::Spacewalk;
+use crate::engine::outcome::GameOutcome;
+use crate::engine::rng::GameRng;
+use crate::engine::shuttle::ShuttleState;
+use crate::engine::state::GameState;
+use crate::engine::vitals::Vitals;
+use crate::entity::furniture::airlock::AirlockControls;
+use crate::entity::furniture::door::{Door, DoorState};
+use crate::entity::furniture::main_terminal::MainTerminal;
+use crate::entity::item::card::{Card, CardStatus};
+use crate::entity::item::ItemId;
+use crate::entity::EntityId;
+use crate::world::room::{Access, Direction, PassageType, RoomIdentifier};
+
+/// Bump this whenever `SaveData` changes shape, and teach `parse` how to
+/// migrate the previous version.
+pub const SAVE_VERSION: u32 = 17;
+
+const SAVE_HEADER: &str = "voidlogue-save";
+const SAVE_DIR: &str = "saves";
+const DEFAULT_SLOT: &str = "quicksave";
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveData {
+    current_room: RoomIdentifier,
+    inventory: Vec<ItemId>,
+    rooms: Vec<RoomSave>,
+    containers: Vec<(EntityId, Vec<EntityId>)>,
+    shuttle_state: ShuttleState,
+    outcome: Option<GameOutcome>,
+    seed: u64,
+    /// How many words were drawn from the seeded RNG.
+    rng_word_pos: u64,
+    /// Missing from version 3 saves, which load with fresh vitals.
+    #[serde(default)]
+    vitals: Vitals,
+    /// Missing before version 5, which starts the clock over.
+    #[serde(default)]
+    turn: u32,
+    /// Whether each container with a lid is open. Before version 6 they're as in the data.
+    #[serde(default)]
+    lids: Vec<(EntityId, bool)>,
+    /// Whether each door is open, closed or locked. Before version 7 they're as in the data.
+    #[serde(default)]
+    doors: Vec<(EntityId, DoorState)>,
+    /// What each card opens, and whether readers still take it. Before version 8 they're as in
+    /// the data.
+    #[serde(default)]
+    cards: Vec<(EntityId, Access, CardStatus)>,
+    /// What the player has on. Before version 9 nothing could be worn.
+    #[serde(default)]
+    worn: Vec<ItemId>,
+    /// Whether each airlock chamber holds air. Before version 10 they're as in the data.
+    #[serde(default)]
+    airlocks: Vec<(EntityId, bool)>,
+    /// How much air is left in each tank. Before version 11 tanks were bottomless, and load full.
+    #[serde(default)]
+    tanks: Vec<(EntityId, u8)>,
+    /// Where the player floats outside the shuttle. Before version 11 they're at the hatch.
+    #[serde(default)]
+    spacewalk: Option<Spacewalk>,
+    /// Which terminals are in maintenance mode. Before version 17 the shuttle kept it, see
+    /// `v16`.
+    #[serde(default)]
+    terminals: Vec<(EntityId, bool)>,
+}
+
+/// Before version 17 maintenance mode was a switch on the shuttle, for the one terminal it had.
+mod v16 {
+    use serde::Deserialize;
+
+    use crate::entity::furniture::FurnId;
+    use crate::entity::EntityId;
+
+    #[derive(Deserialize)]
+    pub struct SaveData {
+        shuttle_state: ShuttleState,
+    }
+
+    #[derive(Deserialize)]
+    struct ShuttleState {
+        /// Missing before version 15, which were played before the lockout and stay unlocked.
+        #[serde(default = "unlocked")]
+        maintenance_mode: bool,
+    }
+
+    fn unlocked() -> bool {
+        true
+    }
+
+    impl SaveData {
+        pub fn migrate(self, save: &mut super::SaveData) {
+            save.terminals = vec![(
+                EntityId::Furniture(FurnId::MainTerminal),
+                self.shuttle_state.maintenance_mode,
+            )];
+        }
+    }
+}
+
+/// Version 2 didn't record the RNG, so a loaded game gets a fresh seed.
+mod v2 {
+    use serde::Deserialize;
+
+    use super::{RoomSave, ShuttleState};
+    use crate::engine::outcome::GameOutcome;
+    use crate::engine::vitals::Vitals;
+    use crate::entity::item::ItemId;
+    use crate::entity::EntityId;
+    use crate::world::room::RoomIdentifier;
+
+    #[derive(Deserialize)]
+    pub struct SaveData {
+        pub(super) current_room: RoomIdentifier,
+        pub(super) inventory: Vec<ItemId>,
+        pub(super) rooms: Vec<RoomSave>,
+        pub(super) containers: Vec<(EntityId, Vec<EntityId>)>,
+        pub(super) shuttle_state: ShuttleState,
+        pub(super) outcome: Option<GameOutcome>,
+    }
+
+    impl From<SaveData> for super::SaveData {
+        fn from(save: SaveData) -> Self {
+            super::SaveData {
+                current_room: save.current_room,
+                inventory: save.inventory,
+                rooms: save.rooms,
+                containers: save.containers,
+                shuttle_state: save.shuttle_state,
+                outcome: save.outcome,
+                seed: rand::random(),
+                rng_word_pos: 0,
+                vitals: Vitals::default(),
+                turn: 0,
+                lids: Vec::new(),
+                doors: Vec::new(),
+                cards: Vec::new(),
+                worn: Vec::new(),
+                airlocks: Vec::new(),
+                tanks: Vec::new(),
+                spacewalk: None,
+                terminals: Vec::new(),
+            }
+        }
+    }
+}
+
+/// Version 1 only knew whether the player got lost in space.
+mod v1 {
+    use serde::Deserialize;
+
+    use super::{v2, RoomSave, ShuttleState};
+    use crate::engine::outcome::GameOutcome;
+    use crate::entity::item::ItemId;
+    use crate::entity::EntityId;
+    use crate::world::room::RoomIdentifier;
+
+    #[derive(Deserialize)]
+    pub struct SaveData {
+        current_room: RoomIdentifier,
+        inventory: Vec<ItemId>,
+        rooms: Vec<RoomSave>,
+        containers: Vec<(EntityId, Vec<EntityId>)>,
+        shuttle_state: ShuttleState,
+        lost: bool,
+    }
+
+    impl From<SaveData> for v2::SaveData {
+        fn from(save: SaveData) -> Self {
+            v2::SaveData {
+                current_room: save.current_room,
+                inventory: save.inventory,
+                rooms: save.rooms,
+                containers: save.containers,
+                shuttle_state: save.shuttle_state,
+                outcome: save.lost.then_some(GameOutcome::LostInSpace),
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RoomSave {
+    id: RoomIdentifier,
+    visited: bool,
+    entities: Vec<EntityId>,
+    connected_rooms: Vec<(Direction, PassageType, RoomIdentifier)>,
+}
+
+pub fn save(game_state: &GameState, slot: Option<&str>) -> Result<String, String> {
+    let slot = slot.unwrap_or(DEFAULT_SLOT);
+    let path = slot_path(slot)?;
+    let contents = serialize(game_state)?;
+
+    fs::create_dir_all(SAVE_DIR).map_err(|e| format!("Can't create {}: {}", SAVE_DIR, e))?;
+    fs::write(&path, contents).map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+
+    Ok(slot.to_string())
+}
+
+pub fn load(slot: Option<&str>, data_dir: Option<&Path>) -> Result<GameState, String> {
+    let slot = slot.unwrap_or(DEFAULT_SLOT);
+    let path = slot_path(slot)?;
+    let contents = fs::read_to_string(&path)
+        .map_err(|_| format!("There's no saved game in slot '{}'.", slot))?;
+
+    restore(parse(&contents)?, data_dir)
+}
+
+pub fn serialize(game_state: &GameState) -> Result<String, String> {
+    let body = ron::ser::to_string_pretty(&snapshot(game_state), ron::ser::PrettyConfig::new())
+        .map_err(|e| format!("Can't serialize the game: {}", e))?;
+
+    Ok(format!("{} {}\n{}\n", SAVE_HEADER, SAVE_VERSION, body))
+}
+
+pub fn deserialize(contents: &str) -> Result<GameState, String> {
+    restore(parse(contents)?, None)
+}
+
+fn parse(contents: &str) -> Result<SaveData, String> {
+    let (header, body) = contents.split_once('\n').unwrap_or((contents, ""));
+    let version = header
+        .strip_prefix(SAVE_HEADER)
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .ok_or("This doesn't look like a save file.")?;
+
+    let corrupted = |e: ron::error::SpannedError| format!("The save file is corrupted: {}", e);
+    let mut save = match version {
+        3..=SAVE_VERSION => ron::from_str(body).map_err(corrupted),
+        2 => ron::from_str::<v2::SaveData>(body)
+            .map(SaveData::from)
+            .map_err(corrupted),
+        1 => ron::from_str::<v1::SaveData>(body)
+            .map(|save| SaveData::from(v2::SaveData::from(save)))
+            .map_err(corrupted),
+        v if v > SAVE_VERSION => Err(format!(
+            "The save file is from a newer version of the game (save version {}).",
+            v
+        )),
+        v => Err(format!("Save version {} is not supported anymore.", v)),
+    }?;
+    if version < 17 {
+        ron::from_str::<v16::SaveData>(body)
+            .map_err(corrupted)?
+            .migrate(&mut save);
+    }
+    Ok(save)
+}
+
+fn snapshot(game_state: &GameState) -> SaveData {
+    let mut rooms: Vec<RoomSave> = game_state
+        .world
+        .rooms
+        .iter()
+        .map(|(id, room)| RoomSave {
+            id: *id,
+            visited: room.visited,
+            entities: room.entities.clone(),
+            connected_rooms: room.connected_rooms.clone(),
+        })
+        .collect();
+    rooms.sort_by_key(|room| room.id);
+
+    let mut containers: Vec<(EntityId, Vec<EntityId>)> = game_state
+        .world
+        .entities
+        .iter()
+        .filter_map(|(id, entity)| {
+            entity
+                .as_containable()
+                .map(|container| (*id, container.contains().clone()))
+        })
+        .collect();
+    containers.sort_by_key(|(id, _)| *id);
+
+    let mut lids: Vec<(EntityId, bool)> = game_state
+        .world
+        .entities
+        .iter()
+        .filter(|(_, entity)| Door::as_door(entity.as_ref()).is_none())
+        .filter_map(|(id, entity)| entity.as_openable().map(|lid| (*id, lid.is_open())))
+        .collect();
+    lids.sort_by_key(|(id, _)| *id);
+
+    let mut doors: Vec<(EntityId, DoorState)> = game_state
+        .world
+        .entities
+        .iter()
+        .filter_map(|(id, entity)| Door::as_door(entity.as_ref()).map(|door| (*id, door.state())))
+        .collect();
+    doors.sort_by_key(|(id, _)| *id);
+
+    let mut cards: Vec<(EntityId, Access, CardStatus)> = game_state
+        .world
+        .entities
+        .iter()
+        .filter_map(|(id, entity)| {
+            Card::as_card(entity.as_ref()).map(|card| (*id, card.access(), card.status()))
+        })
+        .collect();
+    cards.sort_by_key(|(id, _, _)| *id);
+
+    let mut airlocks: Vec<(EntityId, bool)> = game_state
+        .world
+        .entities
+        .iter()
+        .filter_map(|(id, entity)| {
+            AirlockControls::as_airlock(entity.as_ref())
+                .map(|airlock| (*id, airlock.is_pressurized()))
+        })
+        .collect();
+    airlocks.sort_by_key(|(id, _)| *id);
+
+    let mut tanks: Vec<(EntityId, u8)> = game_state
+        .world
+        .entities
+        .iter()
+        .filter_map(|(id, entity)| entity.as_wearable().map(|gear| (*id, gear)))
+        .filter(|(_, gear)| gear.tank() > 0)
+        .map(|(id, gear)| (id, gear.air()))
+        .collect();
+    tanks.sort_by_key(|(id, _)| *id);
+
+    let mut terminals: Vec<(EntityId, bool)> = game_state
+        .world
+        .entities
+        .iter()
+        .filter_map(|(id, entity)| {
+            MainTerminal::as_terminal(entity.as_ref())
+                .map(|terminal| (*id, !terminal.is_suspended()))
+        })
+        .collect();
+    terminals.sort_by_key(|(id, _)| *id);
+
+    SaveData {
+        current_room: game_state.current_room,
+        inventory: game_state.inventory.clone(),
+        rooms,
+        containers,
+        shuttle_state: game_state.shuttle_state.clone(),
+        outcome: game_state.outcome.clone(),
+        seed: game_state.rng.seed(),
+        // A game would have to run for ages to draw 2^64 words.
+        rng_word_pos: game_state.rng.word_pos() as u64,
+        vitals: game_state.vitals.clone(),
+        turn: game_state.turn,
+        lids,
+        doors,
+        cards,
+        worn: game_state.worn.clone(),
+        airlocks,
+        tanks,
+        spacewalk: game_state.spacewalk.clone(),
+        terminals,
+    }
+}
+
+fn restore(save: SaveData, data_dir: Option<&Path>) -> Result<GameState, String> {
+    let rng = GameRng::resume(save.seed, u128::from(save.rng_word_pos));
+    let mut game_state =
+        GameState::with_rng(save.current_room, rng, data_dir.map(Path::to_path_buf))
+            .map_err(|e| format!("Invalid world data: {}", e))?;
+    game_state.inventory = save.inventory;
+    game_state.worn = save.worn;
+    game_state.spacewalk = save
+        .spacewalk
+        .or_else(|| Spacewalk::start(&game_state.world, save.current_room));
+    game_state.shuttle_state = save.shuttle_state;
+    game_state.outcome = save.outcome;
+    game_state.vitals = save.vitals;
+    game_state.turn = save.turn;
+    clock::replay_descriptions(&mut game_state);
+
+    for room_save in save.rooms {
+        if let Some(room) = game_state.world.rooms.get_mut(&room_save.id) {
+            room.visited = room_save.visited;
+            room.entities = room_save.entities;
+            room.connected_rooms = room_save.connected_rooms;
+        }
+    }
+
+    for (container_id, contents) in save.containers {
+        if let Some(container) = game_state.world.get_containable_mut(container_id) {
+            for entity_id in container.contains().clone() {
+                let _ = container.remove(entity_id);
+            }
+            for entity_id in contents {
+                let _ = container.put(entity_id);
+            }
+        }
+    }
+
+    for (entity_id, open) in save.lids {
+        let openable = game_state
+            .world
+            .entities
+            .get_mut(&entity_id)
+            .and_then(|entity| entity.as_openable_mut());
+        if let Some(openable) = openable {
+            if openable.is_open() != open {
+                let _ = if open {
+                    openable.open()
+                } else {
+                    openable.close()
+                };
+            }
+        }
+    }
+
+    for (door_id, state) in save.doors {
+        if let Some(door) = game_state.world.get_door_mut(door_id) {
+            door.set_state(state);
+        }
+    }
+
+    for (card_id, access, status) in save.cards {
+        if let Some(card) = game_state.world.get_card_mut(card_id) {
+            card.restore(access, status);
+        }
+    }
+
+    for (tank_id, air) in save.tanks {
+        let tank = game_state
+            .world
+            .entities
+            .get_mut(&tank_id)
+            .and_then(|entity| entity.as_wearable_mut());
+        if let Some(tank) = tank {
+            tank.set_air(air);
+        }
+    }
+
+    for (airlock_id, pressurized) in save.airlocks {
+        let airlock = game_state
+            .world
+            .entities
+            .get_mut(&airlock_id)
+            .and_then(|entity| AirlockControls::as_airlock_mut(entity.as_mut()));
+        if let Some(airlock) = airlock {
+            airlock.set_pressurized(pressurized);
+        }
+    }
+
+    for (terminal_id, maintenance_mode) in save.terminals {
+        if let Some(terminal) = game_state.world.get_terminal_mut(terminal_id) {
+            terminal.set_maintenance_mode(maintenance_mode);
+        }
+    }
+
+    Ok(game_state)
+}
+
+fn slot_path(slot: &str) -> Result<PathBuf, String> {
+    if slot.is_empty()
+        || !slot
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "'{}' is not a valid slot name. Use letters, digits, '-' and '_'.",
+            slot
+        ));
+    }
+
+    Ok(PathBuf::from(SAVE_DIR).join(format!("{}.ron", slot)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::furniture::FurnId;
+
+    const V1: &str = include_str!("../../tests/fixtures/saves/v1.ron");
+    const V2: &str = include_str!("../../tests/fixtures/saves/v2.ron");
+    const V4: &str = include_str!("../../tests/fixtures/saves/v4.ron");
+    const V15: &str = include_str!("../../tests/fixtures/saves/v15.ron");
+
+    fn main_terminal(game_state: &GameState) -> &MainTerminal {
+        let terminal = EntityId::Furniture(FurnId::MainTerminal);
+        game_state.world.get_terminal(terminal).unwrap()
+    }
+
+    fn play(commands: &[&str]) -> GameState {
+        let mut game_state = GameState::with_seed(RoomIdentifier::Storage, 39).unwrap();
+        for command in commands {
+            crate::process_line(&mut game_state, command);
+        }
+        game_state
+    }
+
+    #[test]
+    fn restores_the_state_it_saved() {
+        let game_state = play(&["e", "s", "e", "n", "open desk", "take card from desk", "z"]);
+        let saved = serialize(&game_state).unwrap();
+        let restored = deserialize(&saved).unwrap();
+
+        assert_eq!(restored.current_room, RoomIdentifier::new("BosunsRoom"));
+        assert_eq!(restored.inventory, game_state.inventory);
+        assert_eq!(restored.turn, game_state.turn);
+        assert_eq!(restored.vitals.hunger, game_state.vitals.hunger);
+        assert_eq!(restored.rng.seed(), 39);
+        assert_eq!(restored.rng.word_pos(), game_state.rng.word_pos());
+        assert_eq!(serialize(&restored).unwrap(), saved);
+    }
+
+    #[test]
+    fn loads_a_save_after_the_game_is_over() {
+        let mut game_state = play(&["e", "save after-game-over"]);
+        game_state.outcome = Some(GameOutcome::Died("testing".to_string()));
+
+        let over = crate::process_line(&mut game_state, "look");
+        assert_eq!(over.to_string(), "The game is over.");
+        assert!(over.outcome.is_some());
+        assert!(crate::process_line(&mut game_state, "help load")
+            .outcome
+            .is_none());
+
+        let loaded = crate::process_line(&mut game_state, "load after-game-over");
+        fs::remove_file(slot_path("after-game-over").unwrap()).unwrap();
+
+        assert!(loaded.to_string().starts_with("Game loaded."));
+        assert_eq!(game_state.outcome, None);
+        assert_eq!(game_state.current_room, RoomIdentifier::new("NorthMess"));
+        assert!(crate::process_line(&mut game_state, "quit").quit);
+    }
+
+    #[test]
+    fn migrates_version_1() {
+        let game_state = deserialize(V1).unwrap();
+        assert_eq!(game_state.current_room, RoomIdentifier::new("NorthMess"));
+        assert_eq!(game_state.outcome, None);
+        assert_eq!(game_state.turn, 0);
+
+        let lost = deserialize(&V1.replace("lost: false", "lost: true")).unwrap();
+        assert_eq!(lost.outcome, Some(GameOutcome::LostInSpace));
+    }
+
+    #[test]
+    fn migrates_version_2() {
+        let game_state = deserialize(V2).unwrap();
+        assert_eq!(game_state.current_room, RoomIdentifier::new("NorthMess"));
+        assert_eq!(game_state.inventory, [ItemId::AssistantCard]);
+        assert_eq!(game_state.outcome, None);
+    }
+
+    #[test]
+    fn fills_in_what_version_4_did_not_record() {
+        let game_state = deserialize(V4).unwrap();
+        assert_eq!(game_state.current_room, RoomIdentifier::new("BosunsRoom"));
+        assert_eq!(game_state.inventory.len(), 2);
+        assert_eq!(game_state.rng.seed(), 39);
+        assert_eq!(game_state.rng.word_pos(), 8);
+        assert_eq!(game_state.vitals.hunger, 36);
+        // Version 5 started counting turns.
+        assert_eq!(game_state.turn, 0);
+        assert!(game_state.worn.is_empty());
+        // The terminal was only locked in version 15.
+        assert!(!main_terminal(&game_state).is_suspended());
+    }
+
+    #[test]
+    fn points_the_shuttle_of_version_15_ahead() {
+        let mut game_state = deserialize(V15).unwrap();
+        let status = game_state
+            .enter_shuttle_command(EntityId::Furniture(FurnId::MainTerminal), "02::09::00")
+            .to_string();
+
+        assert!(status.contains("Heading: X 0, Y 0."), "{status}");
+        assert!(status.contains("Drifting towards X 165, Y 0"), "{status}");
+        assert!(main_terminal(&game_state).is_suspended());
+    }
+
+    #[test]
+    fn rejects_saves_from_newer_versions() {
+        let newer = V4.replacen("voidlogue-save 4", "voidlogue-save 999", 1);
+        assert!(deserialize(&newer).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use std::io::{self, BufRead, Write};
+
+use crate::engine::outcome::GameOutcome;
+use crate::engine::state::GameState;
+use crate::engine::terminal;
+use crate::process_line;
+
+// Exit codes of a scripted run, so CI can tell how a walkthrough ended.
+pub const EXIT_SCRIPT_END: i32 = 0;
+pub const EXIT_WON: i32 = 10;
+pub const EXIT_DIED: i32 = 11;
+pub const EXIT_LOST_IN_SPACE: i32 = 12;
+
+pub fn exit_code(outcome: Option<&GameOutcome>) -> i32 {
+    match outcome {
+        None => EXIT_SCRIPT_END,
+        Some(GameOutcome::Won) => EXIT_WON,
+        Some(GameOutcome::Died(_)) => EXIT_DIED,
+        Some(GameOutcome::LostInSpace) => EXIT_LOST_IN_SPACE,
+    }
+}
+
+/// Plays the commands in `script`, one per line, and writes the transcript to `out`. Blank
+/// lines and lines starting with `#` are skipped. Stops at the first command that ends the game,
+/// or at `quit`.
+pub fn run(
+    game_state: &mut GameState,
+    script: impl BufRead,
+    out: &mut impl Write,
+) -> io::Result<Option<GameOutcome>> {
+    writeln!(out, "{}", game_state.current_room_first_thoughts())?;
+    writeln!(out, "{}", game_state.current_room_description())?;
+
+    for line in script.lines() {
+        let line = line?;
+        let command = line.trim();
+        if command.is_empty() || command.starts_with('#') {
+            continue;
+        }
+
+        let prompt = terminal::prompt(game_state).unwrap_or(">");
+        writeln!(out, "\n{} {}", prompt, command)?;
+        let result = process_line(game_state, command);
+        writeln!(out, "{}", result)?;
+
+        if result.quit {
+            return Ok(None);
+        }
+        if let Some(outcome) = result.outcome {
+            writeln!(out, "{}", outcome)?;
+            return Ok(Some(outcome));
+        }
+    }
+
+    Ok(None)
+}
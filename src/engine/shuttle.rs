@@ -1,6 +1,24 @@
-use crate::entity::furniture::main_terminal::MainTerminalCommand;
-use crate::GameState;
+use serde::{Deserialize, Serialize};
 
+use crate::engine::flight::Flight;
+use crate::engine::outcome::ActionResult;
+use crate::entity::furniture::main_terminal::{MainTerminal, MainTerminalCommand};
+use crate::entity::furniture::FurnId;
+
+/// Meters per turn a burn adds for each level of power.
+const MAIN_DELTA_V: f32 = 20.0;
+const MANEUVER_DELTA_V: f32 = 10.0;
+/// Kilograms of fuel a burn takes for each level of power.
+const MAIN_FUEL: u16 = 25;
+const MANEUVER_FUEL: u16 = 5;
+/// How many of the last commands the shuttle keeps in its log.
+const LOG_LENGTH: usize = 10;
+/// Kilograms of fuel each tank holds when full.
+const TANK_CAPACITY: u16 = 500;
+/// What's left in tank A when the game starts, not even enough for the smallest burn.
+const TANK_A_FUEL: u16 = 3;
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ShuttleState {
     main_engine_on: bool,
     main_engine_power: u8,       // 0-63
@@ -8,14 +26,51 @@ pub struct ShuttleState {
     maneuver_power: u8,          // 0-7
     fuel_tank: FuelTank,
     fuel_pump_on: bool,
+    /// Kilograms of fuel in tanks A and B. Missing from saves before version 13, which start
+    /// with the tanks as they were at the beginning.
+    #[serde(default = "starting_fuel")]
+    fuel: [u16; 2],
+    /// The last commands run, with the turn and the first line of the reply. Missing from
+    /// saves before version 14, which start with an empty log.
+    #[serde(default)]
+    log: Vec<String>,
+    /// Missing from saves before version 12, which start the flight over.
+    #[serde(default)]
+    flight: Flight,
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 enum FuelTank {
     A,
     B,
 }
 
+impl FuelTank {
+    fn name(&self) -> &'static str {
+        match self {
+            FuelTank::A => "A",
+            FuelTank::B => "B",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            FuelTank::A => 0,
+            FuelTank::B => 1,
+        }
+    }
+}
+
+fn starting_fuel() -> [u16; 2] {
+    [TANK_A_FUEL, TANK_CAPACITY]
+}
+
+impl Default for ShuttleState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ShuttleState {
     pub fn new() -> Self {
         ShuttleState {
@@ -25,21 +80,151 @@ impl ShuttleState {
             maneuver_power: 0,
             fuel_tank: FuelTank::A,
             fuel_pump_on: false,
+            fuel: starting_fuel(),
+            log: Vec::new(),
+            flight: Flight::default(),
+        }
+    }
+
+    pub fn is_docked(&self) -> bool {
+        self.flight.is_docked()
+    }
+
+    /// Pushes the shuttle `delta_v` meters per turn towards bearing `x`, `y` without the
+    /// engines, like its decaying orbit does.
+    pub fn push(&mut self, x: i16, y: i16, delta_v: f32) {
+        self.flight.push(x, y, delta_v);
+    }
+
+    /// Lets the shuttle fly on for a turn.
+    pub fn step(&mut self) -> ActionResult {
+        self.flight.step()
+    }
+
+    /// Notes a command that ran in the log. Listing the command set isn't worth a line.
+    pub fn record(&mut self, turn: u32, command: MainTerminalCommand, reply: &ActionResult) {
+        if command == MainTerminalCommand::Help {
+            return;
+        }
+        let reply = reply
+            .messages
+            .first()
+            .and_then(|message| message.lines().next())
+            .unwrap_or_default();
+        self.log
+            .push(format!("T+{:04} {} {}", turn, command, reply));
+        if self.log.len() > LOG_LENGTH {
+            self.log.remove(0);
+        }
+    }
+
+    pub fn log(&self) -> String {
+        if self.log.is_empty() {
+            return "The log is empty.".to_string();
+        }
+        format!("ShuttleControlOS log:\n{}", self.log.join("\n"))
+    }
+
+    /// The status of every subsystem at once.
+    pub fn status(&self) -> String {
+        [
+            self.main_engine_status(),
+            self.maneuver_engine_status(),
+            self.fuel_system_status(),
+        ]
+        .map(|status| match status {
+            Ok(message) | Err(message) => message,
+        })
+        .join("\n")
+    }
+
+    /// What the screen on a fuel tank shows, if `id` is one of the tanks.
+    pub fn fuel_tank_screen(&self, id: FurnId) -> Option<String> {
+        let tank = if id == FurnId::FuelTankA {
+            FuelTank::A
+        } else if id == FurnId::FuelTankB {
+            FuelTank::B
+        } else {
+            return None;
+        };
+        let active = if tank == self.fuel_tank {
+            "Active tank"
+        } else {
+            "Inactive"
+        };
+        let fuel = self.fuel[tank.index()];
+        let level = if fuel == 0 {
+            " Empty."
+        } else if fuel == TANK_CAPACITY {
+            " Full."
+        } else if fuel < TANK_CAPACITY / 10 {
+            " Low fuel."
+        } else {
+            ""
+        };
+        let pump = if tank == self.fuel_tank && self.fuel_pump_on {
+            " Pump running."
+        } else {
+            ""
+        };
+        Some(format!(
+            "The screen says '{}. {} of {} kg.{}{}'",
+            active, fuel, TANK_CAPACITY, level, pump
+        ))
+    }
+
+    /// Takes `amount` kilograms of fuel from the selected tank for a burn, if the pump can
+    /// deliver them.
+    fn draw_fuel(&mut self, amount: u16) -> Result<(), String> {
+        if !self.fuel_pump_on {
+            return Err("fuel pump is off.".to_string());
         }
+        let tank = self.fuel_tank.index();
+        if self.fuel[tank] < amount {
+            return Err(format!(
+                "not enough fuel in tank {}. The burn needs {} kg.",
+                self.fuel_tank.name(),
+                amount
+            ));
+        }
+        self.fuel[tank] -= amount;
+        Ok(())
+    }
+
+    fn has_fuel_flow(&self) -> bool {
+        self.fuel_pump_on && self.fuel[self.fuel_tank.index()] > 0
     }
 
-    pub fn handle_command(&mut self, command: MainTerminalCommand) -> Result<String, String> {
-        match command {
+    /// Runs `command` typed into `terminal`, which has to be in maintenance mode for most of
+    /// them.
+    pub fn handle_command(
+        &mut self,
+        command: MainTerminalCommand,
+        terminal: &mut MainTerminal,
+    ) -> ActionResult {
+        if terminal.is_suspended() && !runs_while_suspended(command) {
+            return ActionResult::failure("ShuttleControlOS: access denied. Your subscription has been suspended. Please top up your account to regain control of the shuttle.");
+        }
+        let reply = match command {
+            MainTerminalCommand::Help => Ok(MainTerminalCommand::help()),
+            MainTerminalCommand::EnterMaintenanceMode(code) => {
+                terminal.enter_maintenance_mode(code)
+            }
+
             MainTerminalCommand::ActivateMainEngine => self.activate_main_engine(),
             MainTerminalCommand::DeactiveteMainEngine => self.deactivate_main_engine(),
-            MainTerminalCommand::ActivateMainThrust => self.activate_main_thrust(),
+            MainTerminalCommand::ActivateMainThrust => return self.activate_main_thrust(),
             MainTerminalCommand::DeactiveteMainThrust => self.deactivate_main_thrust(),
             MainTerminalCommand::SetMainThrustLevel(level) => self.set_main_thrust_level(level),
             MainTerminalCommand::MainEngineStatus => self.main_engine_status(),
 
             MainTerminalCommand::SetManeurXVector(x) => self.set_maneuver_vector_x(x),
             MainTerminalCommand::SetManeurYVector(y) => self.set_maneuver_vector_y(y),
-            MainTerminalCommand::ActivateManeuverEngines => self.activate_maneuver_engines(),
+            MainTerminalCommand::RotateToManeuverVector => self.rotate_to_maneuver_vector(),
+            MainTerminalCommand::ActivateManeuverEngines => {
+                return self.activate_maneuver_engines()
+            }
+            MainTerminalCommand::DeactivateManeuverEngines => self.deactivate_maneuver_engines(),
             MainTerminalCommand::SetManeuverThrustLevel(level) => {
                 self.set_maneuver_thrust_level(level)
             }
@@ -50,11 +235,13 @@ impl ShuttleState {
             MainTerminalCommand::ActivateFuelPump => self.activate_fuel_pump(),
             MainTerminalCommand::DeactivateFuelPump => self.deactivate_fuel_pump(),
             MainTerminalCommand::FuelSystemStatus => self.fuel_system_status(),
-        }
+        };
+
+        reply.into()
     }
 
     fn activate_main_engine(&mut self) -> Result<String, String> {
-        if self.fuel_tank == FuelTank::B && self.fuel_pump_on {
+        if self.fuel_pump_on && self.fuel[self.fuel_tank.index()] >= MAIN_FUEL {
             self.main_engine_on = true;
             Ok("Main engine started.".to_string())
         } else {
@@ -71,21 +258,30 @@ impl ShuttleState {
         }
     }
 
-    fn activate_main_thrust(&mut self) -> Result<String, String> {
-        if self.main_engine_on && self.main_engine_power > 0 {
-            if self.main_engine_power > 0 && self.main_engine_power <= 10 {
-                //game_state.lost = true;
-                Err("You are mesmerized by the station becoming smaller and smaller as the shuttle slowly drifts further into the deep space, until it completely disappears. You've used up the rest of the fuel, and there's no way back. You'll live for days, maybe weeks, while the life support systems will be slowly shutting off. Maybe there's hope that some ship will still pick you up, maybe not. But you aren't in a good spot for sure. 
-
-You've navigated away from the station, and you are completely lost in space.".to_string())
-            } else {
-                Err("You are thrown in the captains chair. Stars become smears, the Gs feel like an ogre's foot on your chest. You are getting farther and farther into deep space at an alarming rate.
-Suddenly, the accelleration stops. You realize the fuel ran out. The lights blink once again and turn off.
-This is the end.".to_string())
-            }
-            //Ok("Main thrust activated.".to_string())
+    /// The main engine pushes the shuttle the way the maneuver vector points it.
+    fn activate_main_thrust(&mut self) -> ActionResult {
+        if !self.main_engine_on || self.main_engine_power == 0 {
+            return ActionResult::failure(
+                "Cannot activate thrust. Ensure main engine is on and power is set.",
+            );
+        }
+        if self.is_docked() {
+            return ActionResult::failure("Main thrust locked: the shuttle is docked.");
+        }
+        if let Err(error) = self.draw_fuel(u16::from(self.main_engine_power) * MAIN_FUEL) {
+            return ActionResult::failure(format!("Main thrust failure: {}", error));
+        }
+        let (x, y) = self.maneuver_vector;
+        let delta_v = f32::from(self.main_engine_power) * MAIN_DELTA_V;
+        self.flight.burn(x, y, delta_v);
+        if self.main_engine_power <= 10 {
+            format!(
+                "The main engine rumbles, and the shuttle lurches forward. Speed: {:.0} m/turn.",
+                self.flight.speed()
+            )
+            .into()
         } else {
-            Err("Cannot activate thrust. Ensure main engine is on and power is set.".to_string())
+            format!("You are thrown in the captains chair. Stars become smears, the Gs feel like an ogre's foot on your chest. Speed: {:.0} m/turn.", self.flight.speed()).into()
         }
     }
 
@@ -113,7 +309,7 @@ This is the end.".to_string())
     }
 
     fn set_maneuver_vector_x(&mut self, x: i16) -> Result<String, String> {
-        if x < 0 || x > 360 {
+        if !(0..=360).contains(&x) {
             Err("Invalid X vector. Must be between 0 and 360.".to_string())
         } else {
             self.maneuver_vector.0 = x;
@@ -122,7 +318,7 @@ This is the end.".to_string())
     }
 
     fn set_maneuver_vector_y(&mut self, y: i16) -> Result<String, String> {
-        if y < 0 || y > 360 {
+        if !(0..=360).contains(&y) {
             Err("Invalid Y vector. Must be between 0 and 360.".to_string())
         } else {
             self.maneuver_vector.1 = y;
@@ -130,6 +326,32 @@ This is the end.".to_string())
         }
     }
 
+    /// Puffs the maneuver thrusters just enough to turn the shuttle to the maneuver vector,
+    /// without pushing it anywhere.
+    fn rotate_to_maneuver_vector(&mut self) -> Result<String, String> {
+        let (x, y) = self.maneuver_vector;
+        if !self.has_fuel_flow() {
+            return Err("Maneuver engine start failure: No fuel.".to_string());
+        }
+        if self.is_docked() {
+            return Err("Maneuver engines locked: the shuttle is docked.".to_string());
+        }
+        self.draw_fuel(MANEUVER_FUEL)
+            .map_err(|error| format!("Maneuver engine start failure: {}", error))?;
+        self.flight.turn_to(x, y);
+        Ok(format!(
+            "The maneuver thrusters puff, and the shuttle turns to X {}, Y {}.\n{}",
+            x,
+            y,
+            self.navigation_status()
+        ))
+    }
+
+    fn deactivate_maneuver_engines(&mut self) -> Result<String, String> {
+        // Maneuver burns are over as soon as they're done, so there's nothing to stop.
+        Ok("Maneuver thrust deactivated.".to_string())
+    }
+
     fn set_maneuver_thrust_level(&mut self, level: u8) -> Result<String, String> {
         if level > 7 {
             Err("Invalid thrust level for maneuver engines. Must be between 0 and 7.".to_string())
@@ -140,16 +362,41 @@ This is the end.".to_string())
     }
 
     fn maneuver_engine_status(&self) -> Result<String, String> {
-        let status = format!(
+        let mut status = format!(
             "Maneuver engines are {}. X vector: {}. Y vector: {}. Power level: {}.",
             if self.maneuver_power > 0 { "on" } else { "off" },
             self.maneuver_vector.0,
             self.maneuver_vector.1,
             self.maneuver_power
         );
+        status.push('\n');
+        status.push_str(&self.navigation_status());
         Ok(status)
     }
 
+    fn navigation_status(&self) -> String {
+        if self.is_docked() {
+            return "Docked at the station.".to_string();
+        }
+        let ((x, y), distance) = self.flight.station();
+        let (heading_x, heading_y) = self.flight.heading();
+        let mut status = format!(
+            "Station bearing: X {}, Y {}. Distance: {:.0} m. Heading: X {}, Y {}.",
+            x, y, distance, heading_x, heading_y
+        );
+        if self.flight.speed() > 0.0 {
+            let (x, y) = self.flight.drift();
+            status.push_str(&format!(
+                " Drifting towards X {}, Y {} at {:.0} m/turn, closing at {:.0} m/turn.",
+                x,
+                y,
+                self.flight.speed(),
+                self.flight.closing_speed()
+            ));
+        }
+        status
+    }
+
     fn switch_to_fuel_tank_a(&mut self) -> Result<String, String> {
         self.fuel_tank = FuelTank::A;
         Ok("Switched to fuel tank A.".to_string())
@@ -162,67 +409,57 @@ This is the end.".to_string())
 
     fn deactivate_fuel_pump(&mut self) -> Result<String, String> {
         self.fuel_pump_on = false;
+        if self.main_engine_on {
+            self.main_engine_on = false;
+            return Ok("Fuel pump deactivated. Main engine shut down: no fuel flow.".to_string());
+        }
         Ok("Fuel pump deactivated.".to_string())
     }
 
     fn fuel_system_status(&self) -> Result<String, String> {
         let status = format!(
-            "Fuel tank: {}. Fuel pump is {}.",
-            match self.fuel_tank {
-                FuelTank::A => "A",
-                FuelTank::B => "B",
-            },
-            if self.fuel_pump_on { "on" } else { "off" }
+            "Fuel tank: {}. Fuel pump is {}. Tank A: {} of {} kg. Tank B: {} of {} kg.",
+            self.fuel_tank.name(),
+            if self.fuel_pump_on { "on" } else { "off" },
+            self.fuel[FuelTank::A.index()],
+            TANK_CAPACITY,
+            self.fuel[FuelTank::B.index()],
+            TANK_CAPACITY
         );
         Ok(status)
     }
 
-    pub fn activate_maneuver_engines(&mut self) -> Result<String, String> {
+    pub fn activate_maneuver_engines(&mut self) -> ActionResult {
         let x_vector = self.maneuver_vector.0;
         let y_vector = self.maneuver_vector.1;
         let power = self.maneuver_power;
-        if self.fuel_tank != FuelTank::B || !self.fuel_pump_on {
-            return Err("Maneuver engine start failure: No fuel.".to_string());
+        if !self.has_fuel_flow() {
+            return ActionResult::failure("Maneuver engine start failure: No fuel.");
         }
 
-        if x_vector < 0 || x_vector > 360 || y_vector < 0 || y_vector > 360 {
-            return Err(
-                "Invalid vector setting: Out of range. Must be between 0 and 360.".to_string(),
+        if !(0..=360).contains(&x_vector) || !(0..=360).contains(&y_vector) {
+            return ActionResult::failure(
+                "Invalid vector setting: Out of range. Must be between 0 and 360.",
             );
         }
 
-        if power < 0 || power > 7 {
-            return Err("Invalid power setting for maneuver engines.".to_string());
+        if !(0..=7).contains(&power) {
+            return ActionResult::failure("Invalid power setting for maneuver engines.");
         }
 
-        if self.is_correct_approach_for_docking() {
-            Ok("You have successfully maneuvered the shuttle for docking. Congratulations, you've docked at the station! Now let's see what awaits for you next...".to_string())
-        } else {
-            self.check_failed_maneuvering()
+        if self.is_docked() {
+            return ActionResult::failure("Maneuver engines locked: the shuttle is docked.");
         }
-    }
-
-    fn is_correct_approach_for_docking(&self) -> bool {
-        (self.maneuver_vector.0 >= 340 && self.maneuver_vector.0 <= 350)
-            && ((self.maneuver_vector.1 >= 350 && self.maneuver_vector.1 <= 360)
-                || (self.maneuver_vector.1 >= 0 && self.maneuver_vector.1 <= 10))
-            && self.maneuver_power >= 1
-            && self.maneuver_power <= 5
-    }
-
-    fn check_failed_maneuvering(&self) -> Result<String, String> {
-        // Checks for various failed maneuvering scenarios
-        if self.maneuver_vector.0 < 181
-            || self.maneuver_vector.0 > 359
-            || self.maneuver_vector.1 < 240
-            || self.maneuver_vector.1 > 320
-        {
-            Err("You carefully navigate the shuttle... away from the station. Oh no! You have no idea where the station went, as it's no longer visible in any of the view ports. Now you'll die from cold as the shuttle tumbles through the void, no habitable worlds or trade routes for lightyears around you.
-You lost.".to_string())
-        } else {
-            Err("As if in slow motion, the shuttle floats towards the station. Your hope that the approach vector is correct is quickly crushed - just as the shuttle got crushed against the station.
-You lost.".to_string())
+        if power == 0 {
+            return ActionResult::failure("Maneuver engines fire at power 0. Nothing happens.");
+        }
+        if let Err(error) = self.draw_fuel(u16::from(power) * MANEUVER_FUEL) {
+            return ActionResult::failure(format!("Maneuver engine start failure: {}", error));
         }
+
+        self.flight
+            .burn(x_vector, y_vector, f32::from(power) * MANEUVER_DELTA_V);
+        format!("Maneuver burn complete.\n{}", self.navigation_status()).into()
     }
 
     fn switch_to_fuel_tank_b(&mut self) -> Result<String, String> {
@@ -230,3 +467,15 @@ You lost.".to_string())
         Ok("Switched to fuel tank B.".to_string())
     }
 }
+
+/// What a suspended subscription still lets through: looking, and the way out of it.
+fn runs_while_suspended(command: MainTerminalCommand) -> bool {
+    matches!(
+        command,
+        MainTerminalCommand::Help
+            | MainTerminalCommand::EnterMaintenanceMode(_)
+            | MainTerminalCommand::MainEngineStatus
+            | MainTerminalCommand::ManeuverEngineStatus
+            | MainTerminalCommand::FuelSystemStatus
+    )
+}
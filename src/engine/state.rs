@@ -1,21 +1,47 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 
+use crate::engine::disambiguation::Disambiguation;
+use crate::engine::eva::Spacewalk;
+use crate::engine::outcome::{ActionResult, GameOutcome};
+use crate::engine::rng::GameRng;
 use crate::engine::shuttle::ShuttleState;
+use crate::engine::terminal::Session;
+use crate::engine::vitals::Vitals;
 use crate::entity::furniture::main_terminal::MainTerminalCommand;
 use crate::entity::item::ItemId;
-use crate::entity::{Entity, EntityId};
+use crate::entity::EntityId;
 use crate::world::data::World;
-use crate::world::room::{Access, Direction, PassageType, RoomIdentifier};
-
-use strum::IntoEnumIterator;
+use crate::world::loader::DataError;
+use crate::world::room::{Direction, PassageType, RoomIdentifier};
 
 pub struct GameState {
     pub current_room: RoomIdentifier,
     pub room_states: HashMap<RoomIdentifier, RoomState>,
     pub world: World,
+    /// Where `world` was loaded from, so a loaded save gets the same world. `None` for the one
+    /// compiled into the binary.
+    pub data_dir: Option<PathBuf>,
     pub inventory: Vec<ItemId>,
-    shuttle_state: ShuttleState,
-    pub lost: bool,
+    /// What the player has on. Worn things aren't in `inventory`.
+    pub worn: Vec<ItemId>,
+    pub vitals: Vitals,
+    /// Turns passed since the game started. Scheduled events go by it.
+    pub turn: u32,
+    pub(crate) shuttle_state: ShuttleState,
+    /// Where the player floats while outside the shuttle.
+    pub(crate) spacewalk: Option<Spacewalk>,
+    /// The terminal the player is logged in to, if any.
+    pub(crate) terminal: Option<Session>,
+    /// Set once the game is over; the frontend stops taking commands after that.
+    pub outcome: Option<GameOutcome>,
+    pub(crate) disambiguation: Disambiguation,
+    /// What "it" and "them" refer to: the entities the player named or handled last.
+    pub(crate) mentioned: Vec<EntityId>,
+    /// Every random choice draws from this, so a seed replays the same game.
+    pub rng: GameRng,
+    /// Ambient messages draw from this instead, so they never change what the seed plays.
+    pub ambient_rng: GameRng,
 }
 
 pub struct RoomState {
@@ -24,14 +50,26 @@ pub struct RoomState {
 }
 
 impl GameState {
-    pub fn new(starting_room: RoomIdentifier) -> Self {
-        let world = World::initialize();
+    pub fn new(starting_room: RoomIdentifier) -> Result<Self, DataError> {
+        GameState::with_rng(starting_room, GameRng::from_entropy(), None)
+    }
+
+    pub fn with_seed(starting_room: RoomIdentifier, seed: u64) -> Result<Self, DataError> {
+        GameState::with_rng(starting_room, GameRng::new(seed), None)
+    }
+
+    pub fn with_rng(
+        starting_room: RoomIdentifier,
+        rng: GameRng,
+        data_dir: Option<PathBuf>,
+    ) -> Result<Self, DataError> {
+        let world = World::initialize(data_dir.as_deref())?;
 
         let mut room_states: HashMap<RoomIdentifier, RoomState> = HashMap::new();
 
         // Initialize room states with the default values.
-        for room_id in RoomIdentifier::iter() {
-            room_states.insert(room_id, RoomState { is_explored: false });
+        for room_id in world.rooms.keys() {
+            room_states.insert(*room_id, RoomState { is_explored: false });
         }
 
         let inventory = vec![
@@ -39,14 +77,24 @@ impl GameState {
             //ItemId::CaptainCard, // for Debugging
         ];
 
-        GameState {
+        Ok(GameState {
             current_room: starting_room,
             room_states,
             world,
+            data_dir,
             inventory,
+            worn: Vec::new(),
+            vitals: Vitals::default(),
+            turn: 0,
             shuttle_state: ShuttleState::new(),
-            lost: false,
-        }
+            spacewalk: None,
+            terminal: None,
+            outcome: None,
+            disambiguation: Disambiguation::default(),
+            mentioned: Vec::new(),
+            ambient_rng: rng.ambience(),
+            rng,
+        })
     }
 
     pub fn was_current_room_visited(&self) -> bool {
@@ -63,37 +111,35 @@ impl GameState {
         self.world.get_room_entities(&self.current_room)
     }
 
-    pub fn enter_shuttle_command(&mut self, command: &str) -> Result<String, String> {
+    /// Runs a code typed into `terminal`.
+    pub fn enter_shuttle_command(&mut self, terminal: EntityId, command: &str) -> ActionResult {
         match MainTerminalCommand::from_string(command) {
             Ok(parsed_command) => {
-                let result = self.shuttle_state.handle_command(parsed_command);
-                if let Ok(ref message) = result {
-                    if message
-                        .starts_with("You have successfully maneuvered the shuttle for docking.")
-                    {
-                        if let Some(airlock_room) =
-                            self.world.rooms.get_mut(&RoomIdentifier::AirlockA)
-                        {
-                            airlock_room.connected_rooms = vec![(
-                                Direction::North,
-                                PassageType::Free,
-                                RoomIdentifier::StationAirlock,
-                            )];
-                        }
-                    }
-                }
+                let Some(terminal) = self.world.get_terminal_mut(terminal) else {
+                    return ActionResult::failure("There's no terminal to enter that on.");
+                };
+                let result = self.shuttle_state.handle_command(parsed_command, terminal);
+                self.shuttle_state
+                    .record(self.turn, parsed_command, &result);
                 result
             }
-            Err(e) => {
-                if e.starts_with("You are mesmerized")
-                    || e.starts_with("You are thrown")
-                    || e.starts_with("You carefully navigate the shuttle")
-                    || e.starts_with("As if in slow motion")
-                {
-                    self.lost = true;
-                }
-                Err(e.to_string())
+            Err(e) => ActionResult::failure(e),
+        }
+    }
+
+    /// Lets the shuttle fly on for a turn, and opens airlock A onto the station once it docks.
+    pub fn fly_shuttle(&mut self) -> ActionResult {
+        let was_docked = self.shuttle_state.is_docked();
+        let result = self.shuttle_state.step();
+        if !was_docked && self.shuttle_state.is_docked() {
+            if let Some(airlock_room) = self.world.rooms.get_mut(&RoomIdentifier::AirlockA) {
+                airlock_room.connected_rooms = vec![(
+                    Direction::North,
+                    PassageType::Free,
+                    RoomIdentifier::StationAirlock,
+                )];
             }
         }
+        result
     }
 }
@@ -0,0 +1,181 @@
+use crate::engine::outcome::ActionResult;
+use crate::engine::state::GameState;
+use crate::entity::furniture::main_terminal::MainTerminal;
+use crate::entity::EntityId;
+use crate::parser::command::Command;
+
+const PROMPT: &str = "ShuttleControlOS>";
+
+const SHELL_HELP: &str =
+    "Type a code to run it, like 07::09::00. Enter 00::00::00 for the command set.
+- status: engine, maneuver and fuel status at once
+- log: the last commands the shuttle ran
+- history: what you typed since you logged in
+- wait [n]: let turns pass at the terminal
+- exit: log out";
+
+/// A login to a terminal. While it lasts, every line the player types goes to the terminal's
+/// shell instead of the parser.
+pub struct Session {
+    terminal: EntityId,
+    /// Every line typed since logging in, oldest first.
+    history: Vec<String>,
+}
+
+/// What the shell makes of a line: a reply of its own, or a game command to run for it.
+pub enum Input {
+    Reply(ActionResult),
+    Run(Command),
+}
+
+/// The terminal in the room with the player, if there is one.
+pub fn in_reach(game_state: &GameState) -> Option<EntityId> {
+    game_state
+        .current_room_entities()?
+        .iter()
+        .copied()
+        .find(|id| is_terminal(game_state, *id))
+}
+
+/// The terminal a code goes to: the one the player is logged in to, or else the one in the room.
+pub fn current(game_state: &GameState) -> Option<EntityId> {
+    match &game_state.terminal {
+        Some(session) => Some(session.terminal),
+        None => in_reach(game_state),
+    }
+}
+
+pub fn is_terminal(game_state: &GameState, entity_id: EntityId) -> bool {
+    game_state
+        .world
+        .entities
+        .get(&entity_id)
+        .is_some_and(|entity| MainTerminal::as_terminal(entity.as_ref()).is_some())
+}
+
+/// The prompt of the shell the player is logged in to. Outside a session the frontend uses
+/// its own.
+pub fn prompt(game_state: &GameState) -> Option<&'static str> {
+    game_state.terminal.as_ref().map(|_| PROMPT)
+}
+
+pub fn log_in(game_state: &mut GameState, terminal: EntityId) -> String {
+    game_state.terminal = Some(Session {
+        terminal,
+        history: Vec::new(),
+    });
+    format!(
+        "You log in to the {}. ShuttleControlOS is ready for your codes; \"help\" lists what else the shell understands, and \"exit\" logs you out.",
+        terminal_name(game_state, terminal)
+    )
+}
+
+/// Takes a line typed at the terminal's prompt.
+pub fn shell(game_state: &mut GameState, line: &str) -> Input {
+    let Some(session) = game_state.terminal.as_mut() else {
+        return Input::Reply(ActionResult::new());
+    };
+    let line = line.trim();
+    if !line.is_empty() {
+        session.history.push(line.to_string());
+    }
+
+    let mut words = line.split_whitespace();
+    let word = words.next().unwrap_or_default().to_lowercase();
+    let reply = match word.as_str() {
+        "" => ActionResult::new(),
+        "exit" | "logout" | "quit" => {
+            let terminal = session.terminal;
+            game_state.terminal = None;
+            format!(
+                "You log out of the {}.",
+                terminal_name(game_state, terminal)
+            )
+            .into()
+        }
+        "help" => SHELL_HELP.into(),
+        "history" => session
+            .history
+            .iter()
+            .enumerate()
+            .map(|(number, line)| format!("{:>3}  {}", number + 1, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .into(),
+        "log" => game_state.shuttle_state.log().into(),
+        "status" => game_state.shuttle_state.status().into(),
+        "wait" | "z" => return Input::Run(Command::Wait(words.next().map(str::to_string))),
+        _ => return Input::Run(Command::Enter(line.to_string())),
+    };
+    Input::Reply(reply)
+}
+
+fn terminal_name(game_state: &GameState, terminal: EntityId) -> String {
+    game_state
+        .world
+        .entities
+        .get(&terminal)
+        .map_or("terminal".to_string(), |entity| {
+            entity.name().to_lowercase()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+    use crate::engine::rng::GameRng;
+    use crate::entity::furniture::FurnId;
+    use crate::world::loader::{DATA_DIR, ENTITIES_FILE, EVENTS_FILE, ROOMS_FILE};
+    use crate::world::room::RoomIdentifier;
+
+    /// The shipped world with a second terminal in the mess, where the game starts.
+    fn with_mess_terminal() -> GameState {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join(DATA_DIR);
+        let read = |file: &str| fs::read_to_string(data.join(file)).unwrap();
+        let rooms = read(ROOMS_FILE).replacen(
+            "entities: [Furniture(MessTable), Furniture(Counter)],",
+            "entities: [Furniture(MessTable), Furniture(Counter), Furniture(MessTerminal)],",
+            1,
+        );
+        let entities = read(ENTITIES_FILE).replacen(
+            "    (\n        id: Furniture(MainTerminal),",
+            "    (\n        id: Furniture(MessTerminal),\n        name: \"Mess terminal\",\n        kind: MainTerminal(suspended: \"Suspended.\"),\n    ),\n    (\n        id: Furniture(MainTerminal),",
+            1,
+        );
+
+        let dir = std::env::temp_dir().join(format!("voidlogue-terminal-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(ROOMS_FILE), rooms).unwrap();
+        fs::write(dir.join(ENTITIES_FILE), entities).unwrap();
+        fs::write(dir.join(EVENTS_FILE), read(EVENTS_FILE)).unwrap();
+        let game_state = GameState::with_rng(
+            RoomIdentifier::new("Mess"),
+            GameRng::new(39),
+            Some(dir.clone()),
+        );
+        fs::remove_dir_all(&dir).unwrap();
+        game_state.unwrap()
+    }
+
+    fn is_suspended(game_state: &GameState, terminal: &str) -> bool {
+        game_state
+            .world
+            .get_terminal(EntityId::Furniture(FurnId::new(terminal)))
+            .unwrap()
+            .is_suspended()
+    }
+
+    #[test]
+    fn codes_go_to_the_terminal_logged_in_to() {
+        let mut game_state = with_mess_terminal();
+        crate::process_line(&mut game_state, "use mess terminal");
+        let reply = crate::process_line(&mut game_state, "00::07::4721").to_string();
+
+        assert!(reply.starts_with("Service code accepted."), "{reply}");
+        assert!(!is_suspended(&game_state, "MessTerminal"));
+        assert!(is_suspended(&game_state, "MainTerminal"));
+    }
+}
@@ -1,5 +1,8 @@
+use crate::engine::eva;
 use crate::engine::state::GameState;
-use crate::entity::{Entity, EntityId};
+use crate::entity::EntityId;
+use crate::parser::grammar::{self, Form, Verb};
+use crate::parser::suggest;
 
 pub fn inventory(game_state: &GameState) -> String {
     let mut output = "Your inventory contains:\n".to_string();
@@ -18,9 +21,181 @@ pub fn inventory(game_state: &GameState) -> String {
             output.push('.');
         }
     }
+
+    let worn: Vec<String> = game_state
+        .worn
+        .iter()
+        .filter_map(|item_id| game_state.world.entities.get(&EntityId::Item(*item_id)))
+        .map(|entity| entity.name().to_lowercase())
+        .collect();
+    if !worn.is_empty() {
+        output.push_str(&format!("\nYou are wearing: {}.", worn.join(", ")));
+    }
+    output
+}
+
+pub fn status(game_state: &GameState) -> String {
+    let mut output = format!(
+        "You check on yourself. Turns passed: {}.\n{}",
+        game_state.turn,
+        game_state.vitals.report()
+    );
+    if let Some(tank) = eva::tank(game_state) {
+        output.push_str(&format!(
+            "\nSuit air: {} of {} turns left",
+            tank.air(),
+            tank.tank()
+        ));
+    }
+    output
+}
+
+/// Lists every command, or explains `topic` in detail. Both come from the parser's verb
+/// table, so help only ever shows what the parser accepts.
+pub fn help(topic: Option<&str>) -> String {
+    match topic {
+        None => help_overview(),
+        Some(topic) => help_topic(&topic.trim().to_lowercase()),
+    }
+}
+
+fn help_overview() -> String {
+    let mut output = "Enter one of the following commands:\n".to_string();
+    for verb in grammar::VERBS {
+        output.push_str(&format!(
+            "  {} - {}",
+            verb.usages().join(", "),
+            verb.summary
+        ));
+        if let Some(synonyms) = synonyms(verb) {
+            output.push_str(&format!(" Also: {}.", synonyms));
+        }
+        output.push('\n');
+    }
+
+    let (names, shorthands): (Vec<&str>, Vec<&str>) = grammar::DIRECTIONS
+        .iter()
+        .map(|(name, shorthand, _)| (*name, *shorthand))
+        .unzip();
+    output.push_str(&format!(
+        "  {} - Short for \"go <direction>\". Also: {}.\n",
+        names.join(", "),
+        shorthands.join(", ")
+    ));
+    output.push_str("Type \"help <command>\" to learn more about one of them.");
     output
 }
 
-pub fn help() -> String {
-    format!("Enter one of the following commands:")
+fn help_topic(topic: &str) -> String {
+    if let Some((name, shorthand, _)) = grammar::DIRECTIONS
+        .iter()
+        .find(|(name, shorthand, _)| topic == *name || topic == *shorthand)
+    {
+        return format!(
+            "{} - Walk {}. Same as \"go {}\". Also: {}.",
+            name, name, name, shorthand
+        );
+    }
+
+    let Some(verb) = grammar::verb_named(topic) else {
+        let names = grammar::VERBS
+            .iter()
+            .flat_map(|verb| verb.names.iter().copied());
+        return match suggest::closest(topic, names) {
+            Some(name) => format!(
+                "There's no command called \"{}\". Did you mean \"{}\"?",
+                topic, name
+            ),
+            None => format!("There's no command called \"{}\".", topic),
+        };
+    };
+
+    let mut output = format!("{} - {}\nUsage:", verb.names[0], verb.summary);
+    for usage in verb.usages() {
+        output.push_str(&format!("\n  {}", usage));
+    }
+    if let Some(synonyms) = synonyms(verb) {
+        output.push_str(&format!("\nAlso: {}.", synonyms));
+    }
+    for form in verb.forms {
+        if let Form::Indirect { prepositions, .. } = form {
+            if let Some((first, others)) = prepositions.split_first() {
+                output.push_str(&format!(
+                    "\nInstead of \"{}\" you can say: {}.",
+                    first,
+                    others.join(", ")
+                ));
+            }
+        }
+    }
+    output
+}
+
+fn synonyms(verb: &Verb) -> Option<String> {
+    (verb.names.len() > 1).then(|| verb.names[1..].join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_every_verb_and_its_synonyms() {
+        let overview = help(None);
+        for verb in grammar::VERBS {
+            for usage in verb.usages() {
+                assert!(overview.contains(&usage), "help doesn't show \"{usage}\"");
+            }
+            if let Some(synonyms) = synonyms(verb) {
+                assert!(
+                    overview.contains(&format!("Also: {}.", synonyms)),
+                    "help doesn't list the synonyms of \"{}\"",
+                    verb.names[0]
+                );
+            }
+        }
+        for (name, shorthand, _) in grammar::DIRECTIONS {
+            assert!(overview.contains(name) && overview.contains(shorthand));
+        }
+    }
+
+    #[test]
+    fn every_verb_in_help_reaches_a_handler() {
+        use crate::world::room::RoomIdentifier;
+
+        for verb in grammar::VERBS {
+            for form in verb.forms {
+                // "#" is no valid slot, so `save` doesn't write anything.
+                let name = verb.names[0];
+                let input = match form {
+                    Form::Bare(_) => name.to_string(),
+                    Form::Direct(_) | Form::OptionalDirect(_) => format!("{name} #"),
+                    Form::Indirect { prepositions, .. } => {
+                        format!("{name} # {} #", prepositions[0])
+                    }
+                    Form::Direction(_) => format!("{name} north"),
+                };
+                let mut game_state = GameState::with_seed(RoomIdentifier::Storage, 39).unwrap();
+                let reply = crate::process_line(&mut game_state, &input).to_string();
+                assert!(
+                    !crate::UNKNOWN_COMMAND_REACTIONS.contains(&reply.as_str()),
+                    "\"{input}\" is in help, but nothing handles it"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn explains_every_verb_by_any_of_its_names() {
+        for verb in grammar::VERBS {
+            for name in verb.names {
+                let topic = help(Some(name));
+                assert!(
+                    topic.starts_with(&format!("{} - {}", verb.names[0], verb.summary)),
+                    "\"help {name}\" doesn't explain \"{}\"",
+                    verb.names[0]
+                );
+            }
+        }
+    }
 }
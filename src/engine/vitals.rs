@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+
+use crate::engine::outcome::{ActionResult, GameOutcome};
+use crate::entity::item::Nourishment;
+
+pub const MAX: u8 = 100;
+
+// Hunger or thirst from which the body starts running on empty and wears out.
+pub const DRAINING: u8 = 50;
+// How much air a turn without a breathable atmosphere costs.
+const OXYGEN_PER_TURN: u8 = 10;
+
+/// How the player's body is doing. Hunger, thirst and fatigue grow towards `MAX`, oxygen
+/// runs down towards 0; either end is fatal.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Vitals {
+    pub hunger: u8,
+    pub thirst: u8,
+    pub oxygen: u8,
+    pub fatigue: u8,
+}
+
+impl Default for Vitals {
+    /// You wake up in the storage not having eaten in a while.
+    fn default() -> Self {
+        Vitals {
+            hunger: 30,
+            thirst: 20,
+            oxygen: MAX,
+            fatigue: 0,
+        }
+    }
+}
+
+// Warnings given once a vital crosses the level, worst last.
+const HUNGER_WARNINGS: &[(u8, &str)] = &[
+    (60, "Your stomach growls."),
+    (80, "You feel weak from hunger."),
+    (90, "Hunger gnaws at you. You need to eat something, now."),
+];
+const THIRST_WARNINGS: &[(u8, &str)] = &[
+    (60, "Your mouth feels dry."),
+    (80, "Your head throbs. You really need some water."),
+    (
+        90,
+        "Your tongue sticks to your palate. Drink something, anything!",
+    ),
+];
+const FATIGUE_WARNINGS: &[(u8, &str)] = &[
+    (60, "You feel tired."),
+    (80, "Your legs feel heavy, and your eyes keep closing."),
+    (90, "You can barely keep yourself on your feet."),
+];
+// Oxygen warnings count how much air is used up, so they rise like the others.
+const SUFFOCATION_WARNINGS: &[(u8, &str)] = &[
+    (50, "The air tastes stale."),
+    (70, "You're breathing faster, but it doesn't help."),
+    (90, "Dark spots swim before your eyes. You need air!"),
+];
+
+impl Vitals {
+    /// Lets one turn pass. `breathable` is whether the player is in air they can breathe, and
+    /// `burdened` whether they lug around gear that keeps them from catching their breath.
+    pub fn tick(&mut self, breathable: bool, burdened: bool) -> ActionResult {
+        let before = self.clone();
+
+        self.hunger = self.hunger.saturating_add(1).min(MAX);
+        self.thirst = self.thirst.saturating_add(1).min(MAX);
+        self.fatigue = if burdened || self.hunger >= DRAINING || self.thirst >= DRAINING {
+            self.fatigue.saturating_add(1).min(MAX)
+        } else {
+            self.fatigue.saturating_sub(1)
+        };
+        self.oxygen = if breathable {
+            MAX
+        } else {
+            self.oxygen.saturating_sub(OXYGEN_PER_TURN)
+        };
+
+        self.consequences(&before)
+    }
+
+    /// Adds the amounts to the vitals, as a scheduled event does.
+    pub fn adjust(&mut self, hunger: i16, thirst: i16, oxygen: i16, fatigue: i16) -> ActionResult {
+        let before = self.clone();
+        let add = |value: u8, amount: i16| (i16::from(value) + amount).clamp(0, MAX.into()) as u8;
+
+        self.hunger = add(self.hunger, hunger);
+        self.thirst = add(self.thirst, thirst);
+        self.oxygen = add(self.oxygen, oxygen);
+        self.fatigue = add(self.fatigue, fatigue);
+
+        self.consequences(&before)
+    }
+
+    /// The warnings for every level crossed since `before`, and a death if something ran out.
+    fn consequences(&self, before: &Vitals) -> ActionResult {
+        let mut result = ActionResult::new();
+        for (before, after, warnings) in [
+            (before.hunger, self.hunger, HUNGER_WARNINGS),
+            (before.thirst, self.thirst, THIRST_WARNINGS),
+            (before.fatigue, self.fatigue, FATIGUE_WARNINGS),
+            (MAX - before.oxygen, MAX - self.oxygen, SUFFOCATION_WARNINGS),
+        ] {
+            for (level, warning) in warnings {
+                if before < *level && after >= *level {
+                    result.push(*warning);
+                }
+            }
+        }
+
+        match self.cause_of_death() {
+            Some(cause) => {
+                result.push("Your legs give way, and everything turns black.");
+                result.with_outcome(GameOutcome::Died(cause.to_string()))
+            }
+            None => result,
+        }
+    }
+
+    fn cause_of_death(&self) -> Option<&'static str> {
+        if self.oxygen == 0 {
+            Some("suffocation")
+        } else if self.thirst >= MAX {
+            Some("dehydration")
+        } else if self.hunger >= MAX {
+            Some("starvation")
+        } else if self.fatigue >= MAX {
+            Some("exhaustion")
+        } else {
+            None
+        }
+    }
+
+    pub fn nourish(&mut self, nourishment: Nourishment) {
+        self.hunger = self.hunger.saturating_sub(nourishment.food);
+        self.thirst = self.thirst.saturating_sub(nourishment.water);
+    }
+
+    /// One line per vital, in words first and then as a number.
+    pub fn report(&self) -> String {
+        let hunger = match self.hunger {
+            0..=19 => "full",
+            20..=49 => "peckish",
+            50..=79 => "hungry",
+            _ => "starving",
+        };
+        let thirst = match self.thirst {
+            0..=19 => "quenched",
+            20..=49 => "a bit thirsty",
+            50..=79 => "thirsty",
+            _ => "parched",
+        };
+        let oxygen = match self.oxygen {
+            80.. => "breathing easy",
+            50..=79 => "short of breath",
+            _ => "suffocating",
+        };
+        let fatigue = match self.fatigue {
+            0..=19 => "rested",
+            20..=49 => "a bit tired",
+            50..=79 => "tired",
+            _ => "exhausted",
+        };
+
+        format!(
+            "Hunger: {} ({}/{MAX})\nThirst: {} ({}/{MAX})\nOxygen: {} ({}/{MAX})\nFatigue: {} ({}/{MAX})",
+            hunger, self.hunger, thirst, self.thirst, oxygen, self.oxygen, fatigue, self.fatigue
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_turn_makes_you_hungrier_and_thirstier() {
+        let mut vitals = Vitals::default();
+        let result = vitals.tick(true, false);
+
+        assert_eq!((vitals.hunger, vitals.thirst), (31, 21));
+        assert_eq!(vitals.oxygen, MAX);
+        assert!(result.outcome.is_none());
+    }
+
+    #[test]
+    fn you_only_rest_unburdened_and_fed() {
+        let mut vitals = Vitals {
+            fatigue: 10,
+            ..Vitals::default()
+        };
+        vitals.tick(true, false);
+        assert_eq!(vitals.fatigue, 9);
+        vitals.tick(true, true);
+        assert_eq!(vitals.fatigue, 10);
+
+        vitals.hunger = DRAINING;
+        vitals.tick(true, false);
+        assert_eq!(vitals.fatigue, 11);
+    }
+
+    #[test]
+    fn warns_once_when_a_level_is_crossed() {
+        let mut vitals = Vitals {
+            thirst: 59,
+            ..Vitals::default()
+        };
+        assert_eq!(
+            vitals.tick(true, false).to_string(),
+            "Your mouth feels dry."
+        );
+        assert_eq!(vitals.tick(true, false).to_string(), "");
+    }
+
+    #[test]
+    fn vacuum_suffocates() {
+        let mut vitals = Vitals::default();
+        let mut result = ActionResult::new();
+        for _ in 0..MAX / OXYGEN_PER_TURN {
+            result = vitals.tick(false, false);
+        }
+        assert_eq!(
+            result.outcome,
+            Some(GameOutcome::Died("suffocation".to_string()))
+        );
+    }
+
+    #[test]
+    fn eating_and_drinking_take_off_hunger_and_thirst() {
+        let mut vitals = Vitals::default();
+        vitals.nourish(Nourishment { food: 50, water: 5 });
+        assert_eq!((vitals.hunger, vitals.thirst), (0, 15));
+        assert!(vitals.report().starts_with("Hunger: full (0/100)"));
+    }
+}
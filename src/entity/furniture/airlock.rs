@@ -0,0 +1,73 @@
+use std::any::Any;
+
+use super::{Entity, EntityId};
+use crate::impl_entity;
+use crate::world::room::{Direction, RoomIdentifier};
+
+impl_entity!(AirlockControls);
+
+/// The control panel of an airlock chamber. It pumps the air out of `chamber` and back in,
+/// and knows which of the chamber's doors leads inside the shuttle and which one out.
+pub struct AirlockControls {
+    id: EntityId,
+    name: String,
+    aliases: Vec<String>,
+    description: String,
+    chamber: RoomIdentifier,
+    inner: Direction,
+    outer: Direction,
+    pressurized: bool,
+}
+
+impl AirlockControls {
+    pub fn new(
+        id: EntityId,
+        name: String,
+        aliases: Vec<String>,
+        description: String,
+        chamber: RoomIdentifier,
+        doors: (Direction, Direction),
+        pressurized: bool,
+    ) -> Self {
+        AirlockControls {
+            id,
+            name,
+            aliases,
+            description,
+            chamber,
+            inner: doors.0,
+            outer: doors.1,
+            pressurized,
+        }
+    }
+
+    pub fn as_airlock(entity: &dyn Entity) -> Option<&AirlockControls> {
+        entity.as_any().downcast_ref::<AirlockControls>()
+    }
+
+    pub fn as_airlock_mut(entity: &mut dyn Entity) -> Option<&mut AirlockControls> {
+        entity.as_any_mut().downcast_mut::<AirlockControls>()
+    }
+
+    pub fn chamber(&self) -> RoomIdentifier {
+        self.chamber
+    }
+
+    /// The direction of the door back into the shuttle, from inside the chamber.
+    pub fn inner(&self) -> Direction {
+        self.inner
+    }
+
+    /// The direction of the door out into space, from inside the chamber.
+    pub fn outer(&self) -> Direction {
+        self.outer
+    }
+
+    pub fn is_pressurized(&self) -> bool {
+        self.pressurized
+    }
+
+    pub fn set_pressurized(&mut self, pressurized: bool) {
+        self.pressurized = pressurized;
+    }
+}
@@ -0,0 +1,157 @@
+use std::any::Any;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Entity, EntityId};
+use crate::entity::item::Openable;
+use crate::world::room::{Access, Direction, RoomIdentifier};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DoorState {
+    /// Held open, so anyone can walk through.
+    Open,
+    /// Slides aside by itself for anyone whose card the reader accepts.
+    #[default]
+    Closed,
+    /// Stays shut until someone with the right card opens it on purpose.
+    Locked,
+}
+
+/// A door between two rooms, or on one side of a room when there's no coming back. Its card
+/// reader wants `access`; a door with `Access::Broken` is jammed and won't open for anyone.
+pub struct Door {
+    id: EntityId,
+    name: String,
+    aliases: Vec<String>,
+    description: String,
+    access: Access,
+    state: DoorState,
+    /// The rooms the door is in, and in which direction from each.
+    sides: Vec<(RoomIdentifier, Direction)>,
+}
+
+impl Door {
+    pub fn new(
+        id: EntityId,
+        name: String,
+        aliases: Vec<String>,
+        description: String,
+        access: Access,
+        state: DoorState,
+        sides: Vec<(RoomIdentifier, Direction)>,
+    ) -> Self {
+        Door {
+            id,
+            name,
+            aliases,
+            description,
+            access,
+            state,
+            sides,
+        }
+    }
+
+    pub fn as_door(entity: &dyn Entity) -> Option<&Door> {
+        entity.as_any().downcast_ref::<Door>()
+    }
+
+    pub fn as_door_mut(entity: &mut dyn Entity) -> Option<&mut Door> {
+        entity.as_any_mut().downcast_mut::<Door>()
+    }
+
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    pub fn state(&self) -> DoorState {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: DoorState) {
+        self.state = state;
+    }
+
+    pub fn is_jammed(&self) -> bool {
+        self.access == Access::Broken
+    }
+
+    pub fn sides(&self) -> &[(RoomIdentifier, Direction)] {
+        &self.sides
+    }
+
+    /// How the door looks from a room, e.g. "a closed door marked B".
+    pub fn appearance(&self) -> String {
+        if self.is_jammed() {
+            return "a jammed door".to_string();
+        }
+        match (self.state, self.access) {
+            (DoorState::Open, _) => "an open door".to_string(),
+            (DoorState::Closed, Access::None) => "a closed door".to_string(),
+            (DoorState::Closed, access) => format!("a closed door marked {}", access),
+            (DoorState::Locked, Access::None) => "a locked door".to_string(),
+            (DoorState::Locked, access) => format!("a locked door marked {}", access),
+        }
+    }
+}
+
+impl Openable for Door {
+    fn is_open(&self) -> bool {
+        self.state == DoorState::Open
+    }
+
+    fn open(&mut self) -> Result<(), String> {
+        if self.is_jammed() {
+            return Err(format!("The {} is jammed.", self.name.to_lowercase()));
+        }
+        if self.state == DoorState::Open {
+            return Err(format!("The {} is already open.", self.name.to_lowercase()));
+        }
+        self.state = DoorState::Open;
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<(), String> {
+        if self.is_jammed() {
+            return Err(format!("The {} is jammed.", self.name.to_lowercase()));
+        }
+        if self.state != DoorState::Open {
+            return Err(format!(
+                "The {} is already closed.",
+                self.name.to_lowercase()
+            ));
+        }
+        self.state = DoorState::Closed;
+        Ok(())
+    }
+}
+
+impl Entity for Door {
+    fn get_id(&self) -> EntityId {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn as_openable(&self) -> Option<&dyn Openable> {
+        Some(self)
+    }
+    fn as_openable_mut(&mut self) -> Option<&mut dyn Openable> {
+        Some(self)
+    }
+}
@@ -1,8 +1,7 @@
 use std::any::Any;
 
 use super::{Entity, EntityId};
-use crate::entity::item::{Containable, ItemId, Usable};
-use crate::{impl_entity, impl_entity_containable};
+use crate::impl_entity;
 
 impl_entity!(MainTerminal);
 
@@ -11,32 +10,82 @@ pub struct MainTerminal {
     name: String,
     aliases: Vec<String>,
     description: String,
+    #[allow(dead_code)]
     contains: Vec<EntityId>,
+    /// What the display says while the subscription is suspended.
+    suspended: String,
+    /// The maintenance code from the shuttle's service slip.
+    service_code: u16,
+    /// Whether a technician's code has turned on maintenance mode. Until it's on, the
+    /// subscription is suspended and only a few commands run.
+    maintenance_mode: bool,
 }
 
+/// The highest code `00::07` takes: codes have four digits.
+pub const MAX_SERVICE_CODE: u16 = 9999;
+
 impl MainTerminal {
     pub fn new(
         id: EntityId,
         name: String,
         aliases: Vec<String>,
         description: String,
-        contains: Vec<EntityId>,
+        suspended: String,
+        service_code: u16,
     ) -> Self {
         MainTerminal {
             id,
             name,
             aliases,
             description,
-            contains,
+            contains: vec![],
+            suspended,
+            service_code,
+            maintenance_mode: false,
         }
     }
 
-    pub fn enter_command() {
-        todo!()
+    pub fn suspended(&self) -> &str {
+        &self.suspended
+    }
+
+    pub fn service_code(&self) -> u16 {
+        self.service_code
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        !self.maintenance_mode
+    }
+
+    pub fn set_maintenance_mode(&mut self, on: bool) {
+        self.maintenance_mode = on;
+    }
+
+    pub fn enter_maintenance_mode(&mut self, code: u16) -> Result<String, String> {
+        if self.maintenance_mode {
+            Err("Maintenance mode is already on.".to_string())
+        } else if code == self.service_code {
+            self.maintenance_mode = true;
+            Ok("Service code accepted. Maintenance mode on: subscription checks are off until the next service. Manual override commands unlocked.".to_string())
+        } else {
+            Err("Invalid service code. This incident will be reported to the Gerbertt support team.".to_string())
+        }
+    }
+
+    pub fn as_terminal(entity: &dyn Entity) -> Option<&MainTerminal> {
+        entity.as_any().downcast_ref::<MainTerminal>()
+    }
+
+    pub fn as_terminal_mut(entity: &mut dyn Entity) -> Option<&mut MainTerminal> {
+        entity.as_any_mut().downcast_mut::<MainTerminal>()
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MainTerminalCommand {
+    Help,
+    EnterMaintenanceMode(u16),
+
     ActivateMainEngine,
     DeactiveteMainEngine,
     ActivateMainThrust,
@@ -46,7 +95,9 @@ pub enum MainTerminalCommand {
 
     SetManeurXVector(i16),
     SetManeurYVector(i16),
+    RotateToManeuverVector,
     ActivateManeuverEngines,
+    DeactivateManeuverEngines,
     SetManeuverThrustLevel(u8),
     ManeuverEngineStatus,
 
@@ -57,42 +108,214 @@ pub enum MainTerminalCommand {
     FuelSystemStatus,
 }
 
+/// The first field of every code picks the subsystem.
+const SUBSYSTEMS: &[(&str, &str)] = &[
+    ("00", "terminal"),
+    ("01", "main engine"),
+    ("02", "maneuver engine"),
+    ("07", "fuel system"),
+];
+
+/// Every command the terminal takes, in the order `00::00::00` lists them. Commands with an
+/// argument stand in for all of their values.
+const COMMAND_SET: &[(MainTerminalCommand, &str)] = &[
+    (MainTerminalCommand::Help, "list the command set"),
+    (
+        MainTerminalCommand::EnterMaintenanceMode(0),
+        "maintenance mode, for certified service technicians only",
+    ),
+    (
+        MainTerminalCommand::DeactiveteMainEngine,
+        "main engine shut down",
+    ),
+    (MainTerminalCommand::ActivateMainEngine, "main engine start"),
+    (
+        MainTerminalCommand::ActivateMainThrust,
+        "main engine thrust on",
+    ),
+    (
+        MainTerminalCommand::DeactiveteMainThrust,
+        "main engine thrust off",
+    ),
+    (
+        MainTerminalCommand::SetMainThrustLevel(0),
+        "specify thruster power level",
+    ),
+    (MainTerminalCommand::MainEngineStatus, "main engine status"),
+    (MainTerminalCommand::SetManeurXVector(0), "specify x vector"),
+    (MainTerminalCommand::SetManeurYVector(0), "specify y vector"),
+    (
+        MainTerminalCommand::RotateToManeuverVector,
+        "turn the shuttle to the x and y vector",
+    ),
+    (
+        MainTerminalCommand::ActivateManeuverEngines,
+        "maneuvre engine thrust on",
+    ),
+    (
+        MainTerminalCommand::DeactivateManeuverEngines,
+        "maneuvre engine thrust off",
+    ),
+    (
+        MainTerminalCommand::SetManeuverThrustLevel(0),
+        "specify maneuvre thrust power",
+    ),
+    (
+        MainTerminalCommand::ManeuverEngineStatus,
+        "maneuvre engine status",
+    ),
+    (
+        MainTerminalCommand::DeactivateFuelPump,
+        "turn the fuel pump off",
+    ),
+    (
+        MainTerminalCommand::ActivateFuelPump,
+        "turn the fuel pump on",
+    ),
+    (
+        MainTerminalCommand::SwitchToFuelTankA,
+        "switch to fuel tank A",
+    ),
+    (
+        MainTerminalCommand::SwitchToFuelTankB,
+        "switch to fuel tank B",
+    ),
+    (MainTerminalCommand::FuelSystemStatus, "fuel system status"),
+];
+
 impl MainTerminalCommand {
-    pub fn from_string(command_str: &str) -> Result<MainTerminalCommand, &'static str> {
-        match command_str {
-            "01::00::00" => Ok(MainTerminalCommand::DeactiveteMainEngine),
-            "01::00::01" => Ok(MainTerminalCommand::ActivateMainEngine),
-            "01::04::00" => Ok(MainTerminalCommand::ActivateMainThrust),
-            "01::04::01" => Ok(MainTerminalCommand::DeactiveteMainThrust),
-            cmd if cmd.starts_with("01::05::") => {
-                let power = cmd[8..].parse::<u8>().map_err(|_| "Invalid power level")?;
-                Ok(MainTerminalCommand::SetMainThrustLevel(power))
-            }
-            "01::09::00" => Ok(MainTerminalCommand::MainEngineStatus),
+    /// The code without the argument, if the command takes one.
+    fn code(&self) -> &'static str {
+        match self {
+            MainTerminalCommand::Help => "00::00::00",
+            MainTerminalCommand::EnterMaintenanceMode(_) => "00::07",
+            MainTerminalCommand::DeactiveteMainEngine => "01::00::00",
+            MainTerminalCommand::ActivateMainEngine => "01::00::01",
+            MainTerminalCommand::ActivateMainThrust => "01::04::00",
+            MainTerminalCommand::DeactiveteMainThrust => "01::04::01",
+            MainTerminalCommand::SetMainThrustLevel(_) => "01::05",
+            MainTerminalCommand::MainEngineStatus => "01::09::00",
+            MainTerminalCommand::SetManeurXVector(_) => "02::02::00",
+            MainTerminalCommand::SetManeurYVector(_) => "02::02::01",
+            MainTerminalCommand::RotateToManeuverVector => "02::03::00",
+            MainTerminalCommand::ActivateManeuverEngines => "02::04::00",
+            MainTerminalCommand::DeactivateManeuverEngines => "02::04::01",
+            MainTerminalCommand::SetManeuverThrustLevel(_) => "02::05",
+            MainTerminalCommand::ManeuverEngineStatus => "02::09::00",
+            MainTerminalCommand::DeactivateFuelPump => "07::00::00",
+            MainTerminalCommand::ActivateFuelPump => "07::00::01",
+            MainTerminalCommand::SwitchToFuelTankA => "07::01::00",
+            MainTerminalCommand::SwitchToFuelTankB => "07::01::01",
+            MainTerminalCommand::FuelSystemStatus => "07::09::00",
+        }
+    }
 
-            cmd if cmd.starts_with("02::02::00::") => {
-                let x_vector = cmd[12..].parse::<i16>().map_err(|_| "Invalid X vector")?;
-                Ok(MainTerminalCommand::SetManeurXVector(x_vector))
-            }
-            cmd if cmd.starts_with("02::02::01::") => {
-                let y_vector = cmd[12..].parse::<i16>().map_err(|_| "Invalid Y vector")?;
-                Ok(MainTerminalCommand::SetManeurYVector(y_vector))
-            }
-            "02::04::00" => Ok(MainTerminalCommand::ActivateManeuverEngines),
-            cmd if cmd.starts_with("02::05::") => {
-                let power = cmd[8..].parse::<u8>().map_err(|_| "Invalid power level")?;
-                Ok(MainTerminalCommand::SetManeuverThrustLevel(power))
-            }
-            "02::09::00" => Ok(MainTerminalCommand::ManeuverEngineStatus),
+    /// What the argument is and which values the shuttle takes, for commands that have one.
+    fn argument(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            MainTerminalCommand::EnterMaintenanceMode(_) => Some(("code", "0 to 9999")),
+            MainTerminalCommand::SetMainThrustLevel(_) => Some(("power", "0 to 63")),
+            MainTerminalCommand::SetManeurXVector(_) => Some(("x", "0 to 360")),
+            MainTerminalCommand::SetManeurYVector(_) => Some(("y", "0 to 360")),
+            MainTerminalCommand::SetManeuverThrustLevel(_) => Some(("power", "0 to 7")),
+            _ => None,
+        }
+    }
 
-            "07::00::00" => Ok(MainTerminalCommand::DeactivateFuelPump),
-            "07::00::01" => Ok(MainTerminalCommand::ActivateFuelPump),
-            "07::01::00" => Ok(MainTerminalCommand::SwitchToFuelTankA),
-            "07::01::01" => Ok(MainTerminalCommand::SwitchToFuelTankB),
-            "07::09::00" => Ok(MainTerminalCommand::FuelSystemStatus),
+    /// The same command with `value` as its argument.
+    fn with_argument(self, value: &str) -> Option<MainTerminalCommand> {
+        match self {
+            MainTerminalCommand::EnterMaintenanceMode(_) => value
+                .parse()
+                .ok()
+                .filter(|code| *code <= MAX_SERVICE_CODE)
+                .map(MainTerminalCommand::EnterMaintenanceMode),
+            MainTerminalCommand::SetMainThrustLevel(_) => value
+                .parse()
+                .ok()
+                .map(MainTerminalCommand::SetMainThrustLevel),
+            MainTerminalCommand::SetManeurXVector(_) => value
+                .parse()
+                .ok()
+                .map(MainTerminalCommand::SetManeurXVector),
+            MainTerminalCommand::SetManeurYVector(_) => value
+                .parse()
+                .ok()
+                .map(MainTerminalCommand::SetManeurYVector),
+            MainTerminalCommand::SetManeuverThrustLevel(_) => value
+                .parse()
+                .ok()
+                .map(MainTerminalCommand::SetManeuverThrustLevel),
+            _ => None,
+        }
+    }
+
+    pub fn from_string(command_str: &str) -> Result<MainTerminalCommand, String> {
+        let fields: Vec<&str> = command_str.split("::").collect();
+        if fields.len() < 2 || fields.iter().any(|field| field.is_empty()) {
+            return Err(format!(
+                "Malformed command \"{}\". Codes look like 01::00::01; enter 00::00::00 for the command set.",
+                command_str
+            ));
+        }
+        let Some((_, subsystem)) = SUBSYSTEMS.iter().find(|(code, _)| *code == fields[0]) else {
+            return Err(format!("Unknown subsystem {}.", fields[0]));
+        };
+
+        for (command, _) in COMMAND_SET {
+            let code: Vec<&str> = command.code().split("::").collect();
+            if !fields.starts_with(&code) {
+                continue;
+            }
+            let rest = &fields[code.len()..];
+            return match (command.argument(), rest) {
+                (None, []) => Ok(*command),
+                (None, _) => Err(format!("{} takes no argument.", command.code())),
+                (Some((name, range)), [value]) => command.with_argument(value).ok_or(format!(
+                    "Invalid {} \"{}\" for {}. Must be a number from {}.",
+                    name,
+                    value,
+                    command.code(),
+                    range
+                )),
+                (Some((name, _)), _) => Err(format!(
+                    "{} takes one argument: {}::<{}>.",
+                    command.code(),
+                    command.code(),
+                    name
+                )),
+            };
+        }
+        Err(format!("Unknown {} command {}.", subsystem, command_str))
+    }
 
-            _ => Err("Invalid command"),
+    /// The listing `00::00::00` prints.
+    pub fn help() -> String {
+        let mut help = "ShuttleControlOS command set:".to_string();
+        for (command, description) in COMMAND_SET {
+            help.push_str(&format!("\n- {}", command.code()));
+            match command.argument() {
+                Some((name, range)) => {
+                    help.push_str(&format!("::<{}> {} ({})", name, description, range))
+                }
+                None => help.push_str(&format!(" {}", description)),
+            }
         }
+        help
+    }
+}
+
+impl std::fmt::Display for MainTerminalCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let argument = match self {
+            MainTerminalCommand::EnterMaintenanceMode(code) => format!("{:04}", code),
+            MainTerminalCommand::SetMainThrustLevel(level)
+            | MainTerminalCommand::SetManeuverThrustLevel(level) => level.to_string(),
+            MainTerminalCommand::SetManeurXVector(vector)
+            | MainTerminalCommand::SetManeurYVector(vector) => vector.to_string(),
+            _ => return f.write_str(self.code()),
+        };
+        write!(f, "{}::{}", self.code(), argument)
     }
 }
 
@@ -103,3 +326,69 @@ impl MainTerminalCommand {
 //         Ok(format!("Command executed: {}", command))
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(command: &str) -> String {
+        MainTerminalCommand::from_string(command).expect_err("the command should not parse")
+    }
+
+    #[test]
+    fn every_command_parses_back_from_its_code() {
+        for (command, _) in COMMAND_SET {
+            assert_eq!(
+                MainTerminalCommand::from_string(&command.to_string()),
+                Ok(*command)
+            );
+        }
+        assert_eq!(
+            MainTerminalCommand::from_string("02::02::00::345"),
+            Ok(MainTerminalCommand::SetManeurXVector(345))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_codes() {
+        for command in ["look", "01", "01::", "::00::01", "01::::01"] {
+            assert_eq!(
+                error(command),
+                format!(
+                    "Malformed command \"{}\". Codes look like 01::00::01; enter 00::00::00 for the command set.",
+                    command
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn names_the_field_that_is_wrong() {
+        assert_eq!(error("05::00::00"), "Unknown subsystem 05.");
+        assert_eq!(
+            error("01::03::00"),
+            "Unknown main engine command 01::03::00."
+        );
+        assert_eq!(error("01::00::01::5"), "01::00::01 takes no argument.");
+        assert_eq!(
+            error("01::05"),
+            "01::05 takes one argument: 01::05::<power>."
+        );
+        assert_eq!(
+            error("02::02::00::10::20"),
+            "02::02::00 takes one argument: 02::02::00::<x>."
+        );
+        assert_eq!(
+            error("01::05::abc"),
+            "Invalid power \"abc\" for 01::05. Must be a number from 0 to 63."
+        );
+        assert_eq!(
+            error("00::07::code"),
+            "Invalid code \"code\" for 00::07. Must be a number from 0 to 9999."
+        );
+        assert_eq!(
+            error("00::07::10000"),
+            "Invalid code \"10000\" for 00::07. Must be a number from 0 to 9999."
+        );
+    }
+}
@@ -1,39 +1,23 @@
+pub mod airlock;
+pub mod door;
 pub mod main_terminal;
 pub mod navigation_computer;
 pub mod sink;
 
 use std::any::Any;
-use strum_macros::EnumIter;
 
-use super::item::{Containable, ItemId};
+use super::item::{Containable, Lid, Openable};
 use super::{Entity, EntityId};
-use crate::{impl_entity, impl_entity_containable};
+use crate::impl_entity_containable;
 
-#[derive(EnumIter, Default, Debug, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum FurnId {
-    Illuminator,
-    StorageShelf,
-    MessTable,
-    Counter,
-    CoffeeMachine,
-    FoodPrinter,
-    Sink,
-    BosunDesk,
-    BookShelves,
-    CaptainsIlluminator,
-    CaptainsDesk,
+string_id!(FurnId);
 
-    NavigationComputer,
-    MainTerminal,
-
-    MainEngine,
-    FuelTankA,
-    FuelTankB,
-    EmergencyLocker,
-
-    WarningSign,
-    #[default]
-    Dust,
+/// Furniture the engine itself refers to. Everything else only exists in the world data.
+#[allow(non_upper_case_globals)]
+impl FurnId {
+    pub const FuelTankA: FurnId = FurnId("FuelTankA");
+    pub const FuelTankB: FurnId = FurnId("FuelTankB");
+    pub const MainTerminal: FurnId = FurnId("MainTerminal");
 }
 
 impl_entity_containable!(Furniture);
@@ -44,6 +28,7 @@ pub struct Furniture {
     aliases: Vec<String>,
     description: String,
     contains: Vec<EntityId>,
+    lid: Lid,
 }
 
 impl Furniture {
@@ -53,6 +38,7 @@ impl Furniture {
         aliases: Vec<String>,
         description: String,
         contains: Vec<EntityId>,
+        lid: Lid,
     ) -> Self {
         Furniture {
             id,
@@ -60,6 +46,7 @@ impl Furniture {
             aliases,
             description,
             contains,
+            lid,
         }
     }
 }
@@ -88,3 +75,15 @@ impl Containable for Furniture {
         &self.contains
     }
 }
+
+impl Openable for Furniture {
+    fn is_open(&self) -> bool {
+        self.lid != Lid::Closed
+    }
+    fn open(&mut self) -> Result<(), String> {
+        self.lid.set(&self.name, true)
+    }
+    fn close(&mut self) -> Result<(), String> {
+        self.lid.set(&self.name, false)
+    }
+}
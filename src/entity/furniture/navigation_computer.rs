@@ -1,14 +1,14 @@
 use std::any::Any;
 
 use super::{Entity, EntityId};
-use crate::entity::item::{Containable, ItemId, Usable};
-use crate::{impl_entity, impl_entity_containable};
+use crate::entity::item::Usable;
 
 pub struct NavigationComputer {
     id: EntityId,
     name: String,
     aliases: Vec<String>,
     description: String,
+    #[allow(dead_code)]
     contains: Vec<EntityId>,
 }
 
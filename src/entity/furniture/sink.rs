@@ -1,18 +1,19 @@
 use std::any::Any;
 
 use super::{Entity, EntityId};
-use crate::entity::item::{Containable, ItemId};
-use crate::{impl_entity, impl_entity_containable};
-
-impl_entity!(Sink);
+use crate::entity::item::{Drinkable, Nourishment};
 
 pub struct Sink {
     id: EntityId,
     name: String,
     aliases: Vec<String>,
     description: String,
+    #[allow(dead_code)]
     contains: Vec<EntityId>,
+    #[allow(dead_code)]
     tap_is_on: bool,
+    /// What a sip from the tap does. The tap never runs dry.
+    nourishment: Nourishment,
 }
 
 impl Sink {
@@ -22,6 +23,7 @@ impl Sink {
         aliases: Vec<String>,
         description: String,
         contains: Vec<EntityId>,
+        nourishment: Nourishment,
     ) -> Self {
         Sink {
             id,
@@ -30,6 +32,44 @@ impl Sink {
             description,
             contains,
             tap_is_on: false,
+            nourishment,
         }
     }
 }
+
+impl Drinkable for Sink {
+    fn drink(&mut self) -> Result<Nourishment, &'static str> {
+        Ok(self.nourishment)
+    }
+}
+
+impl Entity for Sink {
+    fn get_id(&self) -> EntityId {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn as_drinkable(&self) -> Option<&dyn Drinkable> {
+        Some(self)
+    }
+    fn as_drinkable_mut(&mut self) -> Option<&mut dyn Drinkable> {
+        Some(self)
+    }
+}
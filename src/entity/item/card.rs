@@ -0,0 +1,89 @@
+use std::any::Any;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Entity, EntityId};
+use crate::impl_entity;
+use crate::world::room::Access;
+
+impl_entity!(Card);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardStatus {
+    #[default]
+    Valid,
+    /// The shuttle doesn't trust the card anymore; readers refuse it.
+    Revoked,
+    /// The strip is ruined; readers can't make anything of it.
+    Damaged,
+}
+
+/// An access card. Card readers let its holder through doors that ask for `access` or less.
+pub struct Card {
+    id: EntityId,
+    name: String,
+    aliases: Vec<String>,
+    description: String,
+    access: Access,
+    status: CardStatus,
+}
+
+impl Card {
+    pub fn new(
+        id: EntityId,
+        name: String,
+        aliases: Vec<String>,
+        description: String,
+        access: Access,
+    ) -> Self {
+        Card {
+            id,
+            name,
+            aliases,
+            description,
+            access,
+            status: CardStatus::Valid,
+        }
+    }
+
+    pub fn as_card(entity: &dyn Entity) -> Option<&Card> {
+        entity.as_any().downcast_ref::<Card>()
+    }
+
+    pub fn as_card_mut(entity: &mut dyn Entity) -> Option<&mut Card> {
+        entity.as_any_mut().downcast_mut::<Card>()
+    }
+
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    pub fn status(&self) -> CardStatus {
+        self.status
+    }
+
+    /// The access a reader grants for the card, if it accepts it at all.
+    pub fn credential(&self) -> Option<Access> {
+        (self.status == CardStatus::Valid).then_some(self.access)
+    }
+
+    pub fn revoke(&mut self) {
+        self.status = CardStatus::Revoked;
+    }
+
+    pub fn damage(&mut self) {
+        self.status = CardStatus::Damaged;
+    }
+
+    /// Writes another card's credentials onto this one, which makes it good as new.
+    pub fn copy_credentials(&mut self, access: Access) {
+        self.access = access;
+        self.status = CardStatus::Valid;
+    }
+
+    /// Puts back an access level and status recorded in a save.
+    pub fn restore(&mut self, access: Access, status: CardStatus) {
+        self.access = access;
+        self.status = status;
+    }
+}
@@ -1,9 +1,9 @@
 use std::any::Any;
 
-use super::Containable;
 use super::Size;
-use super::{Entity, EntityId, Item, ItemId};
-use crate::{impl_entity, impl_entity_containable};
+use super::{Containable, Lid, Openable};
+use super::{Entity, EntityId};
+use crate::impl_entity_containable;
 
 impl_entity_containable!(Container);
 
@@ -13,6 +13,8 @@ pub struct Container {
     aliases: Vec<String>,
     description: String,
     contains: Vec<EntityId>,
+    lid: Lid,
+    #[allow(dead_code)]
     size: Size,
 }
 
@@ -24,6 +26,7 @@ impl Container {
         description: String,
         contains: Vec<EntityId>,
         size: Size,
+        lid: Lid,
     ) -> Self {
         Container {
             id,
@@ -32,14 +35,12 @@ impl Container {
             description,
             contains,
             size,
+            lid,
         }
     }
     pub fn as_container(entity: &dyn Entity) -> Option<&Container> {
         entity.as_any().downcast_ref::<Container>()
     }
-    fn remove(&mut self, item: ItemId) -> Option<Item> {
-        todo!()
-    }
 }
 
 impl Containable for Container {
@@ -66,3 +67,15 @@ impl Containable for Container {
         &self.contains
     }
 }
+
+impl Openable for Container {
+    fn is_open(&self) -> bool {
+        self.lid != Lid::Closed
+    }
+    fn open(&mut self) -> Result<(), String> {
+        self.lid.set(&self.name, true)
+    }
+    fn close(&mut self) -> Result<(), String> {
+        self.lid.set(&self.name, false)
+    }
+}
@@ -1,32 +1,37 @@
 use std::any::Any;
 
-use super::Edible;
-use super::Size;
-use super::{Entity, EntityId, Item, ItemId};
-use crate::impl_entity;
+use super::{Edible, Nourishment};
+use super::{Entity, EntityId};
 
 pub struct Food {
     id: EntityId,
     name: String,
     aliases: Vec<String>,
     description: String,
+    nourishment: Nourishment,
 }
 
 impl Food {
-    pub fn new(id: EntityId, name: String, aliases: Vec<String>, description: String) -> Self {
+    pub fn new(
+        id: EntityId,
+        name: String,
+        aliases: Vec<String>,
+        description: String,
+        nourishment: Nourishment,
+    ) -> Self {
         Food {
             id,
             name,
             aliases,
             description,
+            nourishment,
         }
     }
 }
 
 impl Edible for Food {
-    fn eat(&mut self) -> Result<(), &'static str> {
-        //println!("You eat the {}.", self.name);
-        Ok(())
+    fn eat(&mut self) -> Result<Nourishment, &'static str> {
+        Ok(self.nourishment)
     }
 }
 
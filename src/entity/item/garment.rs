@@ -0,0 +1,95 @@
+use std::any::Any;
+
+use super::{Entity, EntityId, Protection, Slot, Wearable};
+
+/// Clothing and gear, from a pair of gloves to a space suit.
+pub struct Garment {
+    id: EntityId,
+    name: String,
+    aliases: Vec<String>,
+    description: String,
+    slot: Slot,
+    protection: Protection,
+    bulky: bool,
+    air: u8,
+}
+
+impl Garment {
+    pub fn new(
+        id: EntityId,
+        name: String,
+        aliases: Vec<String>,
+        description: String,
+        slot: Slot,
+        protection: Protection,
+        bulky: bool,
+    ) -> Self {
+        Garment {
+            id,
+            name,
+            aliases,
+            description,
+            slot,
+            protection,
+            bulky,
+            air: protection.air,
+        }
+    }
+}
+
+impl Wearable for Garment {
+    fn slot(&self) -> Slot {
+        self.slot
+    }
+
+    fn seals(&self) -> bool {
+        self.protection.seals
+    }
+
+    fn is_bulky(&self) -> bool {
+        self.bulky
+    }
+
+    fn air(&self) -> u8 {
+        self.air
+    }
+
+    fn tank(&self) -> u8 {
+        self.protection.air
+    }
+
+    fn set_air(&mut self, air: u8) {
+        self.air = air.min(self.protection.air);
+    }
+}
+
+impl Entity for Garment {
+    fn get_id(&self) -> EntityId {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn as_wearable(&self) -> Option<&dyn Wearable> {
+        Some(self)
+    }
+    fn as_wearable_mut(&mut self) -> Option<&mut dyn Wearable> {
+        Some(self)
+    }
+}
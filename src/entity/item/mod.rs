@@ -1,38 +1,25 @@
+pub mod card;
 pub mod container;
 pub mod food;
+pub mod garment;
 pub mod text_item;
 
 use super::{Entity, EntityId};
-use crate::{impl_entity, impl_entity_containable};
+use crate::impl_entity;
+use serde::Deserialize;
 use std::any::Any;
-use strum_macros::{Display, EnumIter};
-
-#[derive(EnumIter, Default, Debug, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum ItemId {
-    Bucket,
-    SpaceRation,
-    WaterBottle,
-    EmptyBottle,
-    SecretBottle,
-    Biscuits,
-    Plate,
-    FoodSurrogateBottle,
-    Fork,
-    LuckyCoin,
-
-    CounterNote,
-    ShuttleManual,
-
-    AssistantCard,
-    BosunCard,
-    CaptainCard,
-
-    SpaceSuit,
+use strum_macros::Display;
 
-    #[default]
-    Dust,
+string_id!(ItemId);
+
+/// Items the engine itself refers to. Every other item only exists in the world data.
+#[allow(non_upper_case_globals)]
+impl ItemId {
+    pub const AssistantCard: ItemId = ItemId("AssistantCard");
+    pub const CaptainCard: ItemId = ItemId("CaptainCard");
 }
 
+#[derive(Deserialize)]
 pub enum Size {
     Small,
     Medium,
@@ -47,13 +34,14 @@ pub enum Liquid {
     Air,
 }
 
-impl_entity!(Item, Drink, SecretBottle);
+impl_entity!(Item, SecretBottle);
 
 pub struct Item {
     id: EntityId,
     name: String,
     aliases: Vec<String>,
     description: String,
+    #[allow(dead_code)]
     size: Size,
     // weight: f32,
     // weight_distribution: f32,
@@ -83,15 +71,13 @@ pub struct SecretBottle {
     name: String,
     aliases: Vec<String>,
     description: String,
+    #[allow(dead_code)]
     contains: Option<EntityId>,
     liquid: Liquid,
 }
 
 impl SecretBottle {
-    fn put(&mut self, item: ItemId) -> Result<(), &'static str> {
-        todo!()
-    }
-
+    #[allow(dead_code)]
     fn remove(&mut self) -> Result<(), String> {
         if self.liquid == Liquid::Air {
             Err("Can't get to it!".to_string())
@@ -141,33 +127,77 @@ impl LiquidContainable for SecretBottle {
     }
 }
 
-#[derive(Debug)]
+/// How much eating or drinking something takes off the player's hunger and thirst.
+#[derive(Clone, Copy, Default)]
+pub struct Nourishment {
+    pub food: u8,
+    pub water: u8,
+}
+
 pub struct Drink {
     id: EntityId,
     name: String,
     aliases: Vec<String>,
     description: String,
+    nourishment: Nourishment,
 }
 
 impl Drink {
-    pub fn new(id: EntityId, name: String, aliases: Vec<String>, description: String) -> Self {
+    pub fn new(
+        id: EntityId,
+        name: String,
+        aliases: Vec<String>,
+        description: String,
+        nourishment: Nourishment,
+    ) -> Self {
         Drink {
             id,
             name,
             aliases,
             description,
+            nourishment,
         }
     }
 }
 
 impl Drinkable for Drink {
-    fn drink(&mut self) -> Result<(), &'static str> {
-        println!("You drink the {}.", self.name);
+    fn drink(&mut self) -> Result<Nourishment, &'static str> {
+        Ok(self.nourishment)
+    }
+}
+
+impl Entity for Drink {
+    fn get_id(&self) -> EntityId {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
 
-        Ok(())
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn as_drinkable(&self) -> Option<&dyn Drinkable> {
+        Some(self)
+    }
+    fn as_drinkable_mut(&mut self) -> Option<&mut dyn Drinkable> {
+        Some(self)
     }
 }
 
+#[allow(dead_code)]
 pub struct LiquidContainer {
     id: EntityId,
     name: String,
@@ -239,8 +269,40 @@ pub trait LiquidContainable {
 }
 
 pub trait Openable {
-    fn open(&mut self) -> Result<(), &'static str>;
-    fn close(&mut self) -> Result<(), &'static str>;
+    fn is_open(&self) -> bool;
+    fn open(&mut self) -> Result<(), String>;
+    fn close(&mut self) -> Result<(), String>;
+}
+
+/// Whether a container has a lid, a door or drawers, and whether they're open.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum Lid {
+    #[default]
+    Absent,
+    Open,
+    Closed,
+}
+
+impl Lid {
+    /// Opens or closes the lid of the container called `name`.
+    pub fn set(&mut self, name: &str, open: bool) -> Result<(), String> {
+        match (*self, open) {
+            (Lid::Open, true) => Err(format!("The {} is already open.", name.to_lowercase())),
+            (Lid::Closed, false) => Err(format!("The {} is already closed.", name.to_lowercase())),
+            (Lid::Absent, _) => Err(format!(
+                "The {} doesn't open or close.",
+                name.to_lowercase()
+            )),
+            (_, true) => {
+                *self = Lid::Open;
+                Ok(())
+            }
+            (_, false) => {
+                *self = Lid::Closed;
+                Ok(())
+            }
+        }
+    }
 }
 
 pub trait Flammable {
@@ -249,11 +311,46 @@ pub trait Flammable {
 }
 
 pub trait Edible {
-    fn eat(&mut self) -> Result<(), &'static str>;
+    fn eat(&mut self) -> Result<Nourishment, &'static str>;
 }
 pub trait Drinkable {
-    fn drink(&mut self) -> Result<(), &'static str>;
+    fn drink(&mut self) -> Result<Nourishment, &'static str>;
+}
+/// Something the player can put on. Gear only protects, or gets in the way, while it's worn.
+pub trait Wearable {
+    fn slot(&self) -> Slot;
+    /// Keeps the vacuum out, and food and drink too.
+    fn seals(&self) -> bool;
+    /// Wears the player out just by moving around in it.
+    fn is_bulky(&self) -> bool;
+    /// Has its own supply of air to breathe, and some of it left.
+    fn supplies_air(&self) -> bool {
+        self.air() > 0
+    }
+    /// How many turns of air are left in its tank.
+    fn air(&self) -> u8;
+    /// How many turns of air its tank holds when full, 0 if it has none.
+    fn tank(&self) -> u8;
+    fn set_air(&mut self, air: u8);
 }
+
+/// What a garment does for the player against the vacuum.
+#[derive(Clone, Copy, Default)]
+pub struct Protection {
+    pub seals: bool,
+    /// Turns of air in the tank.
+    pub air: u8,
+}
+
+/// Where on the body something is worn. Only one thing fits in each slot.
+#[derive(Clone, Copy, PartialEq, Eq, Display, Deserialize)]
+pub enum Slot {
+    Head,
+    Body,
+    Hands,
+    Feet,
+}
+
 pub trait Readable {
     fn read(&mut self) -> Result<&String, &'static str>;
 }
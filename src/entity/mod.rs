@@ -2,11 +2,25 @@ pub mod furniture;
 pub mod item;
 
 use furniture::FurnId;
-use item::{Containable, Edible, ItemId, Readable, Usable};
+use item::{Containable, Drinkable, Edible, ItemId, Openable, Readable, Usable, Wearable};
+use serde::{Deserialize, Serialize};
 use std::any::Any;
-use strum_macros::{Display, EnumIter};
-
-#[derive(EnumIter, Default, Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
+use strum_macros::Display;
+
+#[derive(
+    Default,
+    Debug,
+    Display,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+)]
 pub enum EntityId {
     Item(ItemId),
     Furniture(FurnId),
@@ -33,6 +47,18 @@ pub trait Entity {
     fn as_edible_mut(&mut self) -> Option<&mut dyn Edible> {
         None
     }
+    fn as_openable(&self) -> Option<&dyn Openable> {
+        None
+    }
+    fn as_openable_mut(&mut self) -> Option<&mut dyn Openable> {
+        None
+    }
+    fn as_drinkable(&self) -> Option<&dyn Drinkable> {
+        None
+    }
+    fn as_drinkable_mut(&mut self) -> Option<&mut dyn Drinkable> {
+        None
+    }
     fn as_readable(&self) -> Option<&dyn Readable> {
         None
     }
@@ -45,6 +71,12 @@ pub trait Entity {
     fn as_usable_mut(&mut self) -> Option<&mut dyn Usable> {
         None
     }
+    fn as_wearable(&self) -> Option<&dyn Wearable> {
+        None
+    }
+    fn as_wearable_mut(&mut self) -> Option<&mut dyn Wearable> {
+        None
+    }
 }
 
 pub struct PassiveEntity {
@@ -68,7 +100,7 @@ impl PassiveEntity {
 #[macro_export]
 macro_rules! impl_entity {
     ($($type:ty),*) => {
-        $(impl crate::entity::Entity for $type {
+        $(impl $crate::entity::Entity for $type {
             fn get_id(&self) -> EntityId {
                 self.id
             }
@@ -99,7 +131,7 @@ macro_rules! impl_entity {
 macro_rules! impl_entity_containable {
     ($($type:ty),*) => {
         $(
-            impl crate::entity::Entity for $type {
+            impl $crate::entity::Entity for $type {
                 fn get_id(&self) -> EntityId {
                     self.id
                 }
@@ -122,12 +154,18 @@ macro_rules! impl_entity_containable {
                 fn as_any_mut(&mut self) -> &mut dyn Any {
                     self
                 }
-                fn as_containable(&self) -> Option<&dyn crate::entity::Containable> {
+                fn as_containable(&self) -> Option<&dyn $crate::entity::Containable> {
                     Some(self)
                 }
-                fn as_containable_mut(&mut self) -> Option<&mut dyn crate::entity::Containable> {
+                fn as_containable_mut(&mut self) -> Option<&mut dyn $crate::entity::Containable> {
                     Some(self)
                 }
+                fn as_openable(&self) -> Option<&dyn $crate::entity::item::Openable> {
+                    (self.lid != $crate::entity::item::Lid::Absent).then_some(self)
+                }
+                fn as_openable_mut(&mut self) -> Option<&mut dyn $crate::entity::item::Openable> {
+                    (self.lid != $crate::entity::item::Lid::Absent).then_some(self)
+                }
             }
         )*
     }
@@ -7,28 +7,74 @@ pub mod parser;
 pub mod world;
 
 //use world::World;
-use world::room::Direction;
-use world::room::RoomIdentifier;
 
 use engine::actions;
+use engine::clock;
+use engine::eva;
+use engine::outcome::ActionResult;
+use engine::resolver;
 use engine::state::GameState;
+use engine::terminal;
 use engine::tui;
 use parser::command::Command;
-use rand::prelude::SliceRandom;
+use parser::suggest;
 
-pub fn process_input(game_state: &mut GameState, command: Option<Command>) -> String {
-    match command {
-        Some(Command::Look(None)) => actions::look(&game_state),
+/// Runs one line of player input. Logged in to a terminal, the line goes to its shell. If the
+/// game just asked which of several things the player meant, the line is taken as the answer
+/// first.
+pub fn process_line(game_state: &mut GameState, input: &str) -> ActionResult {
+    if terminal::prompt(game_state).is_some() && game_state.outcome.is_none() {
+        let result = match terminal::shell(game_state, input) {
+            terminal::Input::Reply(result) => result,
+            terminal::Input::Run(command) => process_input(game_state, Some(command)),
+        };
+        if result.outcome.is_some() {
+            game_state.terminal = None;
+        }
+        return result;
+    }
+
+    if game_state.disambiguation.is_pending() {
+        if let Some(command) = game_state.disambiguation.answer(&game_state.world, input) {
+            return process_input(game_state, Some(command));
+        }
+    }
+
+    match parser::command::parse(input) {
+        Some(command) => process_input(game_state, Some(command)),
+        None => {
+            let names = resolver::names_in(game_state, resolver::ALL);
+            match suggest::suggest_command(input, &names) {
+                Some(suggestion) => format!("Did you mean \"{}\"?", suggestion).into(),
+                None => process_input(game_state, None),
+            }
+        }
+    }
+}
+
+/// Runs one command against the game. Once the game has an outcome, every further
+/// command just reports it again, except for loading a saved game, help and quitting.
+pub fn process_input(game_state: &mut GameState, command: Option<Command>) -> ActionResult {
+    if let Some(outcome) = &game_state.outcome {
+        if !matches!(
+            command,
+            Some(Command::Load(_) | Command::Help(_) | Command::Quit)
+        ) {
+            return ActionResult::message("The game is over.").with_outcome(outcome.clone());
+        }
+    }
+
+    let passes_time = command.as_ref().is_some_and(takes_time);
+    let retry = command.clone();
+    let mut result: ActionResult = match command {
+        Some(Command::Look(None)) => actions::look(game_state).into(),
         Some(Command::Look(obj)) => actions::look_at(
             game_state,
             &obj.unwrap_or(String::from("I'm not sure where to look at")),
         ),
         Some(Command::Open(obj)) => actions::open(game_state, &obj),
         Some(Command::Close(obj)) => actions::close(game_state, &obj),
-        Some(Command::Go(direction)) => {
-            handle_movement(game_state, direction);
-            "".to_string()
-        }
+        Some(Command::Go(direction)) => actions::move_in_direction(game_state, direction),
 
         // Interaction
         Some(Command::Take(obj)) => actions::pick_up(game_state, &obj),
@@ -36,48 +82,84 @@ pub fn process_input(game_state: &mut GameState, command: Option<Command>) -> St
         Some(Command::TakeFrom(obj, cont)) => actions::take_from_container(game_state, &obj, &cont),
         Some(Command::PutInto(obj, cont)) => actions::put_into(game_state, &obj, &cont),
         Some(Command::Use(obj)) => actions::r#use(game_state, &obj),
+        Some(Command::UseOn(obj, target)) => actions::use_on(game_state, &obj, &target),
         Some(Command::Enter(command)) => actions::enter(game_state, &command),
         // Combine(String, String), // Combine two items
         // Push(String),         // Push something
-        // Pull(String),         // Pull something
+        Some(Command::Pull(obj)) => eva::pull(game_state, &obj),
         // Turn(String),         // Turn something (like a knob or switch)
         Some(Command::Read(obj)) => actions::read(game_state, &obj),
         Some(Command::Eat(obj)) => actions::eat(game_state, &obj),
+        Some(Command::Drink(obj)) => actions::drink(game_state, &obj),
+        Some(Command::Wear(obj)) => actions::wear(game_state, &obj),
+        Some(Command::Remove(obj)) => actions::remove(game_state, &obj),
+        Some(Command::Cycle(obj)) => actions::cycle(game_state, obj.as_deref()),
+        Some(Command::Thrust(direction)) => eva::thrust(game_state, direction),
+        Some(Command::Clip(obj)) => eva::clip(game_state, obj.as_deref()),
+        Some(Command::Unclip(obj)) => eva::unclip(game_state, obj.as_deref()),
 
         // // Communication
         // // TalkTo(String),       // Talk to a character
         // // Give(String, String), // Give an item to someone
 
         // // Inventory & status
-        Some(Command::Inventory) => tui::inventory(game_state),
-        Some(Command::Help) => tui::help(),
-        // Status,               // Check player's status or health
+        Some(Command::Inventory) => tui::inventory(game_state).into(),
+        Some(Command::Help(verb)) => tui::help(verb.as_deref()).into(),
+        Some(Command::Status) => tui::status(game_state).into(),
+        Some(Command::Wait(turns)) => clock::wait(game_state, turns.as_deref()),
 
         // // Misc
-        // Save,                 // Save the game
-        // Load,                 // Load the game
-        _ => unknown_command_reaction(),
+        Some(Command::Save(slot)) => actions::save(game_state, slot.as_deref()).into(),
+        Some(Command::Load(slot)) => actions::load(game_state, slot.as_deref()).into(),
+        Some(Command::Quit) => ActionResult {
+            quit: true,
+            ..ActionResult::message("Bye!")
+        },
+        None => ActionResult::failure(unknown_command_reaction(game_state)),
+    };
+
+    game_state.disambiguation.finish(retry);
+
+    // A question back to the player doesn't take a turn; the answer will. Neither does a command
+    // that couldn't be done.
+    if passes_time
+        && !result.failed
+        && result.outcome.is_none()
+        && !game_state.disambiguation.is_pending()
+    {
+        let turn = clock::pass_turn(game_state);
+        result.messages.extend(turn.messages);
+        result.outcome = turn.outcome;
+    }
+
+    if result.outcome.is_some() {
+        game_state.outcome.clone_from(&result.outcome);
     }
+    result
 }
 
-fn unknown_command_reaction() -> String {
-    any_of!("Erm, say that again?", 
-    "I am not sure I follow", 
-    "Put what where? Huh?", 
-    "I think I keep hearing voices, maybe I shouldn't have sent those vitnesses of Mandalor away after all.")
+/// Whether a command takes the player a turn. Looking things up in help or saving doesn't,
+/// and waiting counts its own turns.
+fn takes_time(command: &Command) -> bool {
+    !matches!(
+        command,
+        Command::Help(_) | Command::Save(_) | Command::Load(_) | Command::Wait(_) | Command::Quit
+    )
 }
 
-fn handle_movement(game_state: &mut GameState, direction: Direction) {
-    // This function will handle the movement logic.
-    match actions::move_in_direction(game_state, direction) {
-        Ok(new_description) => {
-            println!("{}", new_description);
-        }
-        Err(error) => {
-            print_any!(format!("I can't go to {}: {}", direction, error),
-                       format!("There's nowhere to go at {}, I think?", direction),
-                       format!("I could try, I remember walking through solid walls worked at a certain spaceport platform to get on a spacecruiser to the nanoscience school for gifted kids... or was it in a holoseries?..")
-        );
-        }
-    }
+/// What the player hears back for a command the game doesn't know.
+const UNKNOWN_COMMAND_REACTIONS: &[&str] = &[
+    "Erm, say that again?",
+    "I am not sure I follow",
+    "Put what where? Huh?",
+    "I think I keep hearing voices, maybe I shouldn't have sent those vitnesses of Mandalor away after all.",
+];
+
+fn unknown_command_reaction(game_state: &mut GameState) -> String {
+    use rand::seq::SliceRandom;
+
+    UNKNOWN_COMMAND_REACTIONS
+        .choose(&mut game_state.rng)
+        .map_or("Hrr", |reaction| reaction)
+        .to_string()
 }
@@ -1,22 +1,54 @@
 use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufRead, BufReader as StdBufReader, Write};
+use std::path::PathBuf;
+use std::time::Duration;
 
+use voidlogue::engine::ambient::{self, AmbientEvent};
+use voidlogue::engine::rng::GameRng;
+use voidlogue::engine::script;
 use voidlogue::engine::state::GameState;
-use voidlogue::parser;
-use voidlogue::process_input;
+use voidlogue::engine::terminal;
+use voidlogue::process_line;
+use voidlogue::world::loader;
 use voidlogue::world::room::RoomIdentifier;
 
-use tokio::io::{self, AsyncBufReadExt, BufReader};
+use tokio::io::{self, AsyncBufReadExt, BufReader, Lines, Stdin};
+use tokio::sync::{mpsc, watch};
+use tokio::time::{self, Instant};
 use tokio::{select, signal};
 
+// How often the shuttle gets a chance to make a noise, how long a silence gets before the
+// player starts muttering, and how often an alarm repeats.
+const AMBIENCE_EVERY: Duration = Duration::from_secs(90);
+const IDLE_AFTER: Duration = Duration::from_secs(60);
+const ALARM_EVERY: Duration = Duration::from_secs(20);
+
 #[tokio::main]
 async fn main() {
-    let mut game_state = GameState::new(RoomIdentifier::Storage);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let rng = match option(&args, "--seed") {
+        Some(seed) => match seed.parse::<u64>() {
+            Ok(seed) => GameRng::new(seed),
+            Err(_) => usage_error(&format!("--seed needs a number, not '{seed}'")),
+        },
+        None => GameRng::from_entropy(),
+    };
+    let game_state = GameState::with_rng(RoomIdentifier::Storage, rng, data_dir(&args));
+    let mut game_state = game_state.unwrap_or_else(|e| {
+        eprintln!("Invalid world data: {e}");
+        std::process::exit(1);
+    });
+
+    if let Some(path) = option(&args, "--script") {
+        std::process::exit(run_script(&mut game_state, path));
+    }
 
     // TODO: configure
     let is_cli = true;
 
-    let interface: Box<dyn Interface> = if is_cli {
-        Box::new(Cli)
+    let mut interface: Box<dyn Interface> = if is_cli {
+        Box::new(Cli::new())
     } else {
         unimplemented!("Only CLI is available for now")
     };
@@ -24,6 +56,12 @@ async fn main() {
     interface.post(&game_state.current_room_first_thoughts());
     interface.post(&game_state.current_room_description());
 
+    let (ambient_tx, mut ambient_rx) = mpsc::channel(8);
+    let (activity_tx, activity_rx) = watch::channel(());
+    if !args.iter().any(|arg| arg == "--no-ambient") {
+        spawn_ambience(ambient_tx, activity_rx);
+    }
+
     loop {
         select! {
             _ = signal::ctrl_c() => {
@@ -31,44 +69,159 @@ async fn main() {
                 std::process::exit(0);
             }
             input = interface.get_input() => {
-                // TODO: Add a handler to receive game_lost signal
-                if game_state.lost {
-                    interface.post(&"Game has concluded. Ciao!");
+                let Some(input) = input else {
+                    interface.post(&"Bye!");
                     std::process::exit(0);
-                }
+                };
 
-                // Parse the input to Command
-                let parsed_command = parser::command::parse(&input);
+                let _ = activity_tx.send(());
 
-                // Act on the Command
-                let message = process_input(&mut game_state, parsed_command);
+                // Parse and act on the input
+                let result = process_line(&mut game_state, &input);
 
                 // Display the game response
-                interface.post(&message);
+                interface.post(&result);
+
+                if result.quit {
+                    std::process::exit(0);
+                }
+                if let Some(outcome) = result.outcome {
+                    interface.post(&outcome);
+                    interface.post(&"Game has concluded. You can `load` a saved game, or `quit`.");
+                }
+                if let Some(prompt) = terminal::prompt(&game_state) {
+                    interface.prompt(prompt);
+                }
+            }
+            Some(event) = ambient_rx.recv() => {
+                if let Some(message) = ambient::describe(&mut game_state, event) {
+                    interface.post(&message);
+                }
             }
         }
     }
 }
 
+/// Feeds the main loop with things that happen while the player is still thinking. Every
+/// message on `activity` means the player typed something, which restarts the idle timer.
+fn spawn_ambience(events: mpsc::Sender<AmbientEvent>, mut activity: watch::Receiver<()>) {
+    tokio::spawn(async move {
+        let mut ambience = time::interval_at(Instant::now() + AMBIENCE_EVERY, AMBIENCE_EVERY);
+        let mut alarm = time::interval_at(Instant::now() + ALARM_EVERY, ALARM_EVERY);
+        let idle = time::sleep(IDLE_AFTER);
+        tokio::pin!(idle);
+
+        loop {
+            let event = select! {
+                _ = ambience.tick() => AmbientEvent::Ambience,
+                _ = alarm.tick() => AmbientEvent::Alarm,
+                _ = &mut idle => {
+                    idle.as_mut().reset(Instant::now() + IDLE_AFTER);
+                    AmbientEvent::Idle
+                }
+                changed = activity.changed() => {
+                    if changed.is_err() {
+                        return;
+                    }
+                    idle.as_mut().reset(Instant::now() + IDLE_AFTER);
+                    continue;
+                }
+            };
+            if events.send(event).await.is_err() {
+                return;
+            }
+        }
+    });
+}
+
+/// The value after `name` on the command line. `--script <file>` plays a walkthrough without
+/// prompting (`-` reads it from stdin), `--seed <number>` makes the game replay the same way.
+/// `--no-ambient` keeps the game quiet between commands; scripts always are. `--data <dir>`
+/// loads the world from another directory.
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let position = args.iter().position(|arg| arg == name)?;
+    match args.get(position + 1) {
+        Some(value) => Some(value),
+        None => usage_error(&format!("{name} needs a value")),
+    }
+}
+
+/// `--data <dir>`, or else a `data` directory next to the binary. Without either the game plays
+/// the world compiled into it.
+fn data_dir(args: &[String]) -> Option<PathBuf> {
+    if let Some(dir) = option(args, "--data") {
+        return Some(PathBuf::from(dir));
+    }
+    let dir = std::env::current_exe()
+        .ok()?
+        .parent()?
+        .join(loader::DATA_DIR);
+    dir.is_dir().then_some(dir)
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{message}");
+    eprintln!(
+        "Usage: voidlogue [--seed <number>] [--data <dir>] [--no-ambient] [--script <file|->]"
+    );
+    std::process::exit(2);
+}
+
+fn run_script(game_state: &mut GameState, path: &str) -> i32 {
+    let reader: Box<dyn BufRead> = if path == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        match File::open(path) {
+            Ok(file) => Box::new(StdBufReader::new(file)),
+            Err(e) => {
+                eprintln!("Can't open script {path}: {e}");
+                return 2;
+            }
+        }
+    };
+
+    match script::run(game_state, reader, &mut std::io::stdout().lock()) {
+        Ok(outcome) => script::exit_code(outcome.as_ref()),
+        Err(e) => {
+            eprintln!("Script failed: {e}");
+            2
+        }
+    }
+}
+
 #[async_trait::async_trait]
 trait Interface {
-    async fn get_input(&self) -> String;
+    /// The next line the player typed, or `None` once the input is closed.
+    async fn get_input(&mut self) -> Option<String>;
     fn post(&self, msg: &dyn Display);
+    /// Shows a prompt for the next line, when the game has one of its own.
+    fn prompt(&self, prompt: &str);
 }
 
-struct Cli;
+struct Cli {
+    lines: Lines<BufReader<Stdin>>,
+}
+
+impl Cli {
+    fn new() -> Self {
+        Self {
+            lines: BufReader::new(io::stdin()).lines(),
+        }
+    }
+}
 
 #[async_trait::async_trait]
 impl Interface for Cli {
-    async fn get_input(&self) -> String {
-        let mut input = String::new();
-        let stdin = io::stdin();
-        let mut reader = BufReader::new(stdin);
-        reader.read_line(&mut input).await.unwrap();
-        input
+    async fn get_input(&mut self) -> Option<String> {
+        self.lines.next_line().await.ok().flatten()
     }
 
     fn post(&self, msg: &dyn Display) {
         println!("{msg}");
     }
+
+    fn prompt(&self, prompt: &str) {
+        print!("{prompt} ");
+        let _ = std::io::stdout().flush();
+    }
 }
@@ -1,10 +1,9 @@
-use std::borrow::Cow;
-
+use super::grammar;
 use crate::world::room::Direction;
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum Command {
-    Look(Option<String>),    // Look at something specific
-    Examine(Option<String>), // Examine something in detail
+    Look(Option<String>), // Look at something specific
 
     Open(String),  // Open something
     Close(String), // Close something
@@ -18,84 +17,45 @@ pub enum Command {
     TakeFrom(String, String), // Take from a container
     PutInto(String, String),  // Put into a container
     Use(String),              // Use an object
+    UseOn(String, String),    // Use an object on something, like a card on a reader
     Enter(String),            // Enter a command
-    Combine(String, String),  // Combine two items
-    Push(String),             // Push something
     Pull(String),             // Pull something
-    Turn(String),             // Turn something (like a knob or switch)
     Read(String),             // Read something (like a note)
     Eat(String),              // Eat something that's a food
+    Drink(String),            // Drink something, or from something
+    Wear(String),             // Put on something to wear
+    Remove(String),           // Take off something worn
+    Cycle(Option<String>),    // Pump an airlock's air out, or back in
+    Thrust(Direction),        // Fire the suit's thrusters on a spacewalk
+    Clip(Option<String>),     // Clip the suit's tether on
+    Unclip(Option<String>),   // Unclip the suit's tether
 
     // Communication
     // TalkTo(String),       // Talk to a character
     // Give(String, String), // Give an item to someone
 
     // Inventory & status
-    Inventory, // Check your items
-    Status,    // Check player's status or health
+    Inventory,            // Check your items
+    Status,               // Check player's status or health
+    Wait(Option<String>), // Let some turns pass
 
     // Misc
-    Help, // Show available commands
-    Save, // Save the game
-    Load, // Load the game
+    Help(Option<String>), // Show available commands, or how to use one
+    Save(Option<String>), // Save the game into a slot
+    Load(Option<String>), // Load the game from a slot
+    Quit,                 // Stop playing
 
-          // TODO: always can add more commands lol
+                          // TODO: always can add more commands lol
 }
 
 pub fn parse(input: &str) -> Option<Command> {
     let input_lowercase = input.trim().to_lowercase();
     let words = sanitize_and_split(&input_lowercase);
 
-    match words.iter().as_slice() {
-        ["x", "room", "ls"] => Some(Command::Look(None)),
-        ["look", obj] | ["examine", obj] | ["x", obj] => Some(Command::Look(Some(obj.to_string()))),
-        ["look"] | ["examine"] | ["x"] => Some(Command::Look(None)),
-
-        ["open", obj] | ["o", obj] => Some(Command::Open(obj.to_string())),
-        ["close", obj] | ["c", obj] => Some(Command::Close(obj.to_string())),
-
-        ["take", obj] | ["get", obj] | ["pick", "up", obj] => Some(Command::Take(obj.to_string())),
-        ["take", obj, "from", cont]
-        | ["get", obj, "from", cont]
-        | ["pick", "up", obj, "from", cont]
-        | ["pick", obj, "from", cont]
-        | ["retrieve", obj, "from", cont] => {
-            Some(Command::TakeFrom(obj.to_string(), cont.to_string()))
-        }
-        ["put", obj, "into", cont] | ["place", obj, "into", cont] => {
-            Some(Command::PutInto(obj.to_string(), cont.to_string()))
-        }
-
-        ["drop", obj] => Some(Command::Drop(obj.to_string())),
-        ["use", obj] => Some(Command::Use(obj.to_string())),
-        ["enter", command] => Some(Command::Enter(command.to_string())),
-        //["combine", obj1, "with", obj2] => Some(Command::Combine(obj1.to_string(), obj2.to_string())),
-        //["push", obj] => Some(Command::Push(obj.to_string())),
-        //["pull", obj] => Some(Command::Pull(obj.to_string())),
-        ["turn", obj] => Some(Command::Turn(obj.to_string())),
-        ["read", obj] => Some(Command::Read(obj.to_string())),
-        // ["talk", "to", person] => Some(Command::TalkTo(person.to_string())),
-        // ["give", obj, "to", person] => Some(Command::Give(obj.to_string(), person.to_string())),
-        ["eat", obj] | ["consume", obj] => Some(Command::Eat(obj.to_string())),
-
-        ["inventory"] | ["i"] => Some(Command::Inventory),
-        ["status"] => Some(Command::Status),
-
-        ["help"] | ["h"] => Some(Command::Help),
-        ["save"] => Some(Command::Save),
-        ["load"] => Some(Command::Load),
-
-        ["north"] | ["n"] | ["go", "north"] | ["go", "n"] => Some(Command::Go(Direction::North)),
-        ["east"] | ["e"] | ["go", "east"] | ["go", "e"] => Some(Command::Go(Direction::East)),
-        ["south"] | ["s"] | ["go", "south"] | ["go", "s"] => Some(Command::Go(Direction::South)),
-        ["west"] | ["w"] | ["go", "west"] | ["go", "w"] => Some(Command::Go(Direction::West)),
-        ["up"] | ["u"] | ["go", "up"] | ["go", "u"] => Some(Command::Go(Direction::Up)),
-        ["down"] | ["d"] | ["go", "down"] | ["go", "d"] => Some(Command::Go(Direction::Down)),
-        _ => None,
-    }
+    grammar::parse_words(&words)
 }
 
-fn sanitize_and_split(input: &str) -> Vec<&str> {
+pub(crate) fn sanitize_and_split(input: &str) -> Vec<&str> {
     input
         .split_whitespace()
         .filter(|word| !is_article(word))
@@ -105,14 +65,5 @@ fn sanitize_and_split(input: &str) -> Vec<&str> {
 const ARTICLES: [&str; 3] = ["the", "a", "an"];
 
 fn is_article(word: &str) -> bool {
-    ARTICLES.iter().any(|&a| a == word)
-}
-
-fn strip_articles_and_join(words: &[&str]) -> String {
-    words
-        .iter()
-        .filter(|&word| is_article(word))
-        .cloned()
-        .collect::<Vec<&str>>()
-        .join(" ")
+    ARTICLES.contains(&word)
 }
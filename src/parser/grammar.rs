@@ -0,0 +1,492 @@
+use super::command::Command;
+use crate::world::room::Direction;
+
+/// A verb and the sentence shapes it accepts. `names` may have several words ("pick up"),
+/// the longest name that starts the input wins. The first name is the one help shows.
+pub struct Verb {
+    pub names: &'static [&'static str],
+    /// What help calls the direct object: "take <object>", "enter <code>".
+    pub object: &'static str,
+    pub summary: &'static str,
+    pub forms: &'static [Form],
+}
+
+/// One way to complete a verb. Forms are tried in order, so a form with an indirect object
+/// has to come before the plain direct object one, which would swallow the preposition.
+pub enum Form {
+    /// The verb alone: "inventory".
+    Bare(fn() -> Command),
+    /// The verb and a direct object of any length: "take space suit".
+    Direct(fn(String) -> Command),
+    /// Like `Direct`, but the object may be left out: "look", "look shuttle manual".
+    OptionalDirect(fn(Option<String>) -> Command),
+    /// A direct object, a preposition and an indirect object: "put keys into bucket".
+    Indirect {
+        prepositions: &'static [&'static str],
+        target: &'static str,
+        build: fn(String, String) -> Command,
+    },
+    /// A compass direction: "go north".
+    Direction(fn(Direction) -> Command),
+}
+
+// Prepositions that mean the same thing for the verbs using them.
+pub const FROM: &[&str] = &["from", "out of", "off"];
+pub const INTO: &[&str] = &["into", "in", "inside", "onto", "on"];
+pub const ON: &[&str] = &["on", "at", "with"];
+
+/// Directions with their one-letter shorthands. Either one alone means "go" there.
+pub const DIRECTIONS: &[(&str, &str, Direction)] = &[
+    ("north", "n", Direction::North),
+    ("east", "e", Direction::East),
+    ("south", "s", Direction::South),
+    ("west", "w", Direction::West),
+    ("up", "u", Direction::Up),
+    ("down", "d", Direction::Down),
+];
+
+pub const VERBS: &[Verb] = &[
+    Verb {
+        names: &["look", "look at", "examine", "x", "ls"],
+        object: "object",
+        summary: "Look around the room, or take a closer look at something.",
+        forms: &[Form::OptionalDirect(Command::Look)],
+    },
+    Verb {
+        names: &["open", "o"],
+        object: "object",
+        summary: "Open something.",
+        forms: &[Form::Direct(Command::Open)],
+    },
+    Verb {
+        names: &["close", "c"],
+        object: "object",
+        summary: "Close something.",
+        forms: &[Form::Direct(Command::Close)],
+    },
+    Verb {
+        names: &["go", "walk"],
+        object: "direction",
+        summary: "Walk in a direction.",
+        forms: &[Form::Direction(Command::Go)],
+    },
+    Verb {
+        names: &["take", "get", "pick up", "pick", "retrieve"],
+        object: "object",
+        summary: "Pick something up, or take it out of a container.",
+        forms: &[
+            Form::Indirect {
+                prepositions: FROM,
+                target: "container",
+                build: Command::TakeFrom,
+            },
+            Form::Direct(Command::Take),
+        ],
+    },
+    Verb {
+        names: &["put", "place"],
+        object: "object",
+        summary: "Put something you carry into a container.",
+        forms: &[Form::Indirect {
+            prepositions: INTO,
+            target: "container",
+            build: Command::PutInto,
+        }],
+    },
+    Verb {
+        names: &["drop"],
+        object: "object",
+        summary: "Drop something you carry.",
+        forms: &[Form::Direct(Command::Drop)],
+    },
+    Verb {
+        names: &["use", "swipe"],
+        object: "object",
+        summary: "Use something, like a terminal to log in to it, or use it on something else, like a card on a reader.",
+        forms: &[
+            Form::Indirect {
+                prepositions: ON,
+                target: "target",
+                build: Command::UseOn,
+            },
+            Form::Direct(Command::Use),
+        ],
+    },
+    Verb {
+        names: &["enter", "type"],
+        object: "code",
+        summary: "Type a command code into the terminal in front of you.",
+        forms: &[Form::Direct(Command::Enter)],
+    },
+    Verb {
+        names: &["read"],
+        object: "object",
+        summary: "Read something with writing on it.",
+        forms: &[Form::Direct(Command::Read)],
+    },
+    Verb {
+        names: &["eat", "consume"],
+        object: "object",
+        summary: "Eat something.",
+        forms: &[Form::Direct(Command::Eat)],
+    },
+    Verb {
+        names: &["drink", "drink from", "sip"],
+        object: "object",
+        summary: "Drink something, or from a tap.",
+        forms: &[Form::Direct(Command::Drink)],
+    },
+    Verb {
+        names: &["wear", "put on", "don"],
+        object: "object",
+        summary: "Put on something you carry, like a space suit.",
+        forms: &[Form::Direct(Command::Wear)],
+    },
+    Verb {
+        names: &["remove", "take off", "doff"],
+        object: "object",
+        summary: "Take off something you wear.",
+        forms: &[Form::Direct(Command::Remove)],
+    },
+    Verb {
+        names: &["cycle"],
+        object: "airlock",
+        summary: "Pump the air out of the airlock you're in, or back in.",
+        forms: &[Form::OptionalDirect(Command::Cycle)],
+    },
+    Verb {
+        names: &["thrust", "burst"],
+        object: "direction",
+        summary: "Fire your suit's thrusters on a spacewalk to drift faster that way.",
+        forms: &[Form::Direction(Command::Thrust)],
+    },
+    Verb {
+        names: &["clip", "hook"],
+        object: "tether",
+        summary: "Clip your suit's tether to the handrail by the hatch.",
+        forms: &[Form::OptionalDirect(Command::Clip)],
+    },
+    Verb {
+        names: &["unclip", "unhook"],
+        object: "tether",
+        summary: "Unclip your suit's tether.",
+        forms: &[Form::OptionalDirect(Command::Unclip)],
+    },
+    Verb {
+        names: &["pull", "haul", "tug"],
+        object: "object",
+        summary: "Pull something, like a tether to get back to where it's clipped.",
+        forms: &[Form::Direct(Command::Pull)],
+    },
+    Verb {
+        names: &["inventory", "i"],
+        object: "",
+        summary: "List what you carry and what you wear.",
+        forms: &[Form::Bare(|| Command::Inventory)],
+    },
+    Verb {
+        names: &["status"],
+        object: "",
+        summary: "Check how hungry, thirsty, tired and out of breath you are.",
+        forms: &[Form::Bare(|| Command::Status)],
+    },
+    Verb {
+        names: &["wait", "z"],
+        object: "turns",
+        summary: "Let time pass, one turn unless you say how many.",
+        forms: &[Form::OptionalDirect(Command::Wait)],
+    },
+    Verb {
+        names: &["help", "h"],
+        object: "command",
+        summary: "List the commands, or explain one of them.",
+        forms: &[Form::OptionalDirect(Command::Help)],
+    },
+    Verb {
+        names: &["save"],
+        object: "slot",
+        summary: "Save the game, to the quicksave slot unless you name one.",
+        forms: &[Form::OptionalDirect(Command::Save)],
+    },
+    Verb {
+        names: &["load"],
+        object: "slot",
+        summary: "Load a saved game, from the quicksave slot unless you name one.",
+        forms: &[Form::OptionalDirect(Command::Load)],
+    },
+    Verb {
+        names: &["quit", "q"],
+        object: "",
+        summary: "Stop playing. Anything you haven't saved is lost.",
+        forms: &[Form::Bare(|| Command::Quit)],
+    },
+];
+
+/// Builds a command out of already lowercased words with the articles removed.
+pub fn parse_words(words: &[&str]) -> Option<Command> {
+    // A direction on its own is short for "go <direction>".
+    if let [word] = words {
+        if let Some(direction) = parse_direction(word) {
+            return Some(Command::Go(direction));
+        }
+    }
+
+    let (verb, len) = find_verb(words)?;
+    verb.forms
+        .iter()
+        .find_map(|form| apply(form, &words[len..]))
+}
+
+/// The verb `words` start with, and how many words its name takes.
+pub fn find_verb(words: &[&str]) -> Option<(&'static Verb, usize)> {
+    VERBS
+        .iter()
+        .flat_map(|verb| verb.names.iter().map(move |name| (verb, name)))
+        .filter_map(|(verb, name)| {
+            let name_words: Vec<&str> = name.split_whitespace().collect();
+            words
+                .starts_with(&name_words)
+                .then_some((verb, name_words.len()))
+        })
+        .max_by_key(|(_, len)| *len)
+}
+
+impl Verb {
+    /// The prepositions this verb can take an indirect object with.
+    pub fn prepositions(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.forms.iter().flat_map(|form| match form {
+            Form::Indirect { prepositions, .. } => prepositions.iter().copied(),
+            _ => [].iter().copied(),
+        })
+    }
+
+    /// How each form of the verb is typed, e.g. "take <object> from <container>", simplest
+    /// first.
+    pub fn usages(&self) -> Vec<String> {
+        let name = self.names[0];
+        let mut usages: Vec<String> = self
+            .forms
+            .iter()
+            .map(|form| match form {
+                Form::Bare(_) => name.to_string(),
+                Form::Direct(_) => format!("{} <{}>", name, self.object),
+                Form::OptionalDirect(_) => format!("{} [{}]", name, self.object),
+                Form::Indirect {
+                    prepositions,
+                    target,
+                    ..
+                } => format!(
+                    "{} <{}> {} <{}>",
+                    name, self.object, prepositions[0], target
+                ),
+                Form::Direction(_) => format!("{} <{}>", name, self.object),
+            })
+            .collect();
+        usages.sort_by_key(String::len);
+        usages
+    }
+}
+
+/// The verb one of whose names is exactly `name`.
+pub fn verb_named(name: &str) -> Option<&'static Verb> {
+    VERBS.iter().find(|verb| verb.names.contains(&name))
+}
+
+fn apply(form: &Form, rest: &[&str]) -> Option<Command> {
+    match form {
+        Form::Bare(build) => rest.is_empty().then(build),
+        Form::Direct(build) => noun_phrase(rest).map(build),
+        Form::OptionalDirect(build) => Some(build(noun_phrase(rest))),
+        Form::Indirect {
+            prepositions,
+            build,
+            ..
+        } => {
+            let (object, target) = split_at_preposition(rest, prepositions)?;
+            Some(build(noun_phrase(object)?, noun_phrase(target)?))
+        }
+        Form::Direction(build) => match rest {
+            [word] => parse_direction(word).map(build),
+            _ => None,
+        },
+    }
+}
+
+fn noun_phrase(words: &[&str]) -> Option<String> {
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+/// Splits at the first preposition that has words on both sides of it.
+fn split_at_preposition<'a, 'w>(
+    words: &'a [&'w str],
+    prepositions: &[&str],
+) -> Option<(&'a [&'w str], &'a [&'w str])> {
+    (1..words.len()).find_map(|i| {
+        prepositions.iter().find_map(|preposition| {
+            let preposition: Vec<&str> = preposition.split_whitespace().collect();
+            let end = i + preposition.len();
+            (words[i..].starts_with(&preposition) && end < words.len())
+                .then(|| (&words[..i], &words[end..]))
+        })
+    })
+}
+
+fn parse_direction(word: &str) -> Option<Direction> {
+    DIRECTIONS
+        .iter()
+        .find(|(name, short, _)| word == *name || word == *short)
+        .map(|(_, _, direction)| *direction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Option<Command> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        parse_words(&words)
+    }
+
+    fn object(name: &str) -> String {
+        name.to_string()
+    }
+
+    #[test]
+    fn bare_verbs_take_nothing_after_them() {
+        assert_eq!(parse("inventory"), Some(Command::Inventory));
+        assert_eq!(parse("i"), Some(Command::Inventory));
+        assert_eq!(parse("status"), Some(Command::Status));
+        assert_eq!(parse("status report"), None);
+    }
+
+    #[test]
+    fn direct_objects_keep_every_word() {
+        assert_eq!(
+            parse("take space suit"),
+            Some(Command::Take(object("space suit")))
+        );
+        assert_eq!(parse("open desk"), Some(Command::Open(object("desk"))));
+        assert_eq!(parse("close desk"), Some(Command::Close(object("desk"))));
+        assert_eq!(parse("drop bucket"), Some(Command::Drop(object("bucket"))));
+        assert_eq!(parse("read slip"), Some(Command::Read(object("slip"))));
+        assert_eq!(
+            parse("eat biscuits"),
+            Some(Command::Eat(object("biscuits")))
+        );
+        assert_eq!(parse("turn knob"), None);
+        assert_eq!(parse("pull tether"), Some(Command::Pull(object("tether"))));
+        assert_eq!(parse("wear suit"), Some(Command::Wear(object("suit"))));
+        assert_eq!(parse("remove suit"), Some(Command::Remove(object("suit"))));
+        assert_eq!(
+            parse("enter 02::01::00"),
+            Some(Command::Enter(object("02::01::00")))
+        );
+        assert_eq!(parse("take"), None);
+    }
+
+    #[test]
+    fn optional_objects_may_be_left_out() {
+        assert_eq!(parse("look"), Some(Command::Look(None)));
+        assert_eq!(
+            parse("examine shuttle manual"),
+            Some(Command::Look(Some(object("shuttle manual"))))
+        );
+        assert_eq!(parse("wait 3"), Some(Command::Wait(Some(object("3")))));
+        assert_eq!(parse("cycle"), Some(Command::Cycle(None)));
+        assert_eq!(parse("clip"), Some(Command::Clip(None)));
+        assert_eq!(
+            parse("unclip tether"),
+            Some(Command::Unclip(Some(object("tether"))))
+        );
+        assert_eq!(
+            parse("help take"),
+            Some(Command::Help(Some(object("take"))))
+        );
+        assert_eq!(parse("save"), Some(Command::Save(None)));
+        assert_eq!(
+            parse("load slot"),
+            Some(Command::Load(Some(object("slot"))))
+        );
+    }
+
+    #[test]
+    fn directions_go_with_or_without_a_verb() {
+        assert_eq!(parse("n"), Some(Command::Go(Direction::North)));
+        assert_eq!(parse("go east"), Some(Command::Go(Direction::East)));
+        assert_eq!(parse("walk d"), Some(Command::Go(Direction::Down)));
+        assert_eq!(parse("thrust up"), Some(Command::Thrust(Direction::Up)));
+        assert_eq!(parse("go nowhere"), None);
+        assert_eq!(parse("go north east"), None);
+    }
+
+    #[test]
+    fn every_preposition_splits_off_the_target() {
+        for preposition in FROM {
+            assert_eq!(
+                parse(&format!("take bosun card {preposition} desk")),
+                Some(Command::TakeFrom(object("bosun card"), object("desk"))),
+                "take ... {preposition} ..."
+            );
+        }
+        for preposition in INTO {
+            assert_eq!(
+                parse(&format!("put fork {preposition} bucket")),
+                Some(Command::PutInto(object("fork"), object("bucket"))),
+                "put ... {preposition} ..."
+            );
+        }
+        for preposition in ON {
+            assert_eq!(
+                parse(&format!("use card {preposition} card reader")),
+                Some(Command::UseOn(object("card"), object("card reader"))),
+                "use ... {preposition} ..."
+            );
+        }
+    }
+
+    #[test]
+    fn a_preposition_needs_words_on_both_sides() {
+        assert_eq!(
+            parse("take from desk"),
+            Some(Command::Take(object("from desk")))
+        );
+        assert_eq!(
+            parse("take card from"),
+            Some(Command::Take(object("card from")))
+        );
+        assert_eq!(parse("put fork"), None);
+        assert_eq!(parse("use card"), Some(Command::Use(object("card"))));
+    }
+
+    #[test]
+    fn the_longest_verb_name_wins() {
+        assert_eq!(parse("put on suit"), Some(Command::Wear(object("suit"))));
+        assert_eq!(
+            parse("put suit on hook"),
+            Some(Command::PutInto(object("suit"), object("hook")))
+        );
+        assert_eq!(
+            parse("take off suit"),
+            Some(Command::Remove(object("suit")))
+        );
+        assert_eq!(
+            parse("take suit off hook"),
+            Some(Command::TakeFrom(object("suit"), object("hook")))
+        );
+        assert_eq!(parse("pick up fork"), Some(Command::Take(object("fork"))));
+        assert_eq!(
+            parse("look at desk"),
+            Some(Command::Look(Some(object("desk"))))
+        );
+        assert_eq!(
+            parse("drink from sink"),
+            Some(Command::Drink(object("sink")))
+        );
+    }
+
+    #[test]
+    fn unknown_verbs_parse_to_nothing() {
+        assert_eq!(parse("dance"), None);
+        assert_eq!(parse(""), None);
+    }
+}
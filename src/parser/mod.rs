@@ -1 +1,3 @@
 pub mod command;
+pub mod grammar;
+pub mod suggest;
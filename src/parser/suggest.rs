@@ -0,0 +1,153 @@
+use super::command::{parse, sanitize_and_split};
+use super::grammar::{self, DIRECTIONS, VERBS};
+use crate::utils::edit_distance;
+
+// Words that stand for objects without naming them, so there's nothing to correct.
+const STAND_INS: [&str; 5] = ["all", "everything", "it", "them", "that"];
+
+/// The candidate closest to `word`, if it's close enough to be a typo of it.
+pub fn closest<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let allowed = match word.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        6..=9 => 2,
+        _ => 3,
+    };
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(word, candidate), candidate))
+        .filter(|(distance, _)| *distance <= allowed)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Rewrites input the parser didn't understand into the closest command it would understand,
+/// correcting the verb and the object names. `names` are the names the player can refer to
+/// right now, so a suggestion never gives away something they can't see.
+pub fn suggest_command(input: &str, names: &[String]) -> Option<String> {
+    let input = input.trim().to_lowercase();
+    let words = sanitize_and_split(&input);
+    let (first, after_first) = words.split_first()?;
+
+    let (verb, verb_name, rest) = match grammar::find_verb(&words) {
+        Some((verb, len)) => (Some(verb), words[..len].join(" "), &words[len..]),
+        None => {
+            let single_word_verbs = VERBS
+                .iter()
+                .flat_map(|verb| verb.names.iter().copied())
+                .filter(|name| !name.contains(' '));
+            let name = closest(first, single_word_verbs.chain(direction_names()))?;
+            let verb = grammar::find_verb(&[name]).map(|(verb, _)| verb);
+            (verb, name.to_string(), after_first)
+        }
+    };
+
+    let prepositions: Vec<&str> = verb
+        .map(|verb| verb.prepositions().collect())
+        .unwrap_or_default();
+    let mut suggestion = verb_name;
+    if !rest.is_empty() {
+        suggestion.push(' ');
+        suggestion.push_str(&correct_phrase(rest, names, &prepositions));
+    }
+
+    (suggestion != words.join(" ") && parse(&suggestion).is_some()).then_some(suggestion)
+}
+
+/// Corrects the object names in `words`, and the prepositions between them.
+fn correct_phrase(words: &[&str], names: &[String], prepositions: &[&str]) -> String {
+    let found = words.iter().enumerate().skip(1).find_map(|(i, word)| {
+        let preposition = if prepositions.contains(word) {
+            Some(*word)
+        } else {
+            closest(word, prepositions.iter().copied())
+        };
+        preposition.map(|preposition| (i, preposition))
+    });
+
+    match found {
+        Some((i, preposition)) => format!(
+            "{} {} {}",
+            correct_name(&words[..i], names),
+            preposition,
+            correct_phrase(&words[i + 1..], names, prepositions)
+        ),
+        None => correct_name(words, names),
+    }
+}
+
+fn correct_name(words: &[&str], names: &[String]) -> String {
+    let name = words.join(" ");
+    if STAND_INS.contains(&name.as_str()) || names.contains(&name) {
+        return name;
+    }
+
+    let candidates = names.iter().map(String::as_str).chain(direction_names());
+    closest(&name, candidates).map_or(name.clone(), str::to_string)
+}
+
+fn direction_names<'a>() -> impl Iterator<Item = &'a str> {
+    DIRECTIONS.iter().map(|(name, _, _)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_more_typos_in_longer_words() {
+        // Up to two letters nothing is a typo, then one edit, two from six letters and three
+        // from ten.
+        assert_eq!(closest("gu", ["go"]), None);
+        assert_eq!(closest("lok", ["look"]), Some("look"));
+        assert_eq!(closest("lk", ["look"]), None);
+        assert_eq!(closest("bxcxet", ["bucket"]), Some("bucket"));
+        assert_eq!(closest("bxcxxt", ["bucket"]), None);
+        assert_eq!(closest("spcae sut", ["space suit"]), Some("space suit"));
+        assert_eq!(closest("spxae sxxt", ["space suit"]), None);
+        assert_eq!(
+            closest("captin's crd", ["captain's card"]),
+            Some("captain's card")
+        );
+    }
+
+    #[test]
+    fn picks_the_closest_candidate() {
+        assert_eq!(closest("cart", ["cord", "card"]), Some("card"));
+        assert_eq!(closest("desk", ["dusk", "desk"]), Some("desk"));
+    }
+
+    #[test]
+    fn corrects_the_verb() {
+        let names = ["bucket".to_string()];
+        assert_eq!(
+            suggest_command("taek bucket", &names).as_deref(),
+            Some("take bucket")
+        );
+        assert_eq!(suggest_command("nroth", &names).as_deref(), Some("north"));
+        assert_eq!(suggest_command("dance", &names), None);
+    }
+
+    #[test]
+    fn corrects_names_and_prepositions() {
+        let names = ["bucket".to_string(), "fork".to_string()];
+        assert_eq!(
+            suggest_command("put frok itno bucket", &names).as_deref(),
+            Some("put fork into bucket")
+        );
+        assert_eq!(
+            suggest_command("taek it", &names).as_deref(),
+            Some("take it")
+        );
+    }
+
+    #[test]
+    fn only_suggests_names_in_reach() {
+        let names = ["bucket".to_string()];
+        assert_eq!(
+            suggest_command("taek spaec suit", &names).as_deref(),
+            Some("take spaec suit")
+        );
+    }
+}
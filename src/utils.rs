@@ -1,17 +1,168 @@
-#[macro_export]
-macro_rules! print_any {
-    ($($x:expr),*) => {{
-        let mut rng = rand::thread_rng();
-        let messages: Vec<String> = vec![$($x.to_string()),*];
-        println!("{}", messages.choose(&mut rng).unwrap_or(&"Hmhm".to_string()));
-    }};
-}
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
 
+/// Picks one of the messages with the game's RNG: `any_of!(&mut game_state.rng; "a", "b")`.
 #[macro_export]
 macro_rules! any_of {
-    ($($x:expr),*) => {{
-        let mut rng = rand::thread_rng();
+    ($rng:expr; $($x:expr),*) => {{
+        use rand::seq::SliceRandom;
+
         let messages = &[$($x),*];
-        format!("{}", messages.choose(&mut rng).unwrap_or(&"Hrr"))
+        match messages.choose($rng) {
+            Some(message) => message.to_string(),
+            None => "Hrr".to_string(),
+        }
     }};
 }
+
+/// Declares a `Copy` identifier backed by an interned name, so that ids can come from data
+/// files while the engine still refers to a few well-known ones as associated constants.
+/// In RON the id is written as a bare identifier, e.g. `Storage` or `Item(Bucket)`.
+#[macro_export]
+macro_rules! string_id {
+    ($name:ident) => {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+        pub struct $name(&'static str);
+
+        impl $name {
+            pub fn new(name: &str) -> Self {
+                $name($crate::utils::intern(name))
+            }
+
+            pub fn as_str(&self) -> &'static str {
+                self.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_unit_variant(stringify!($name), 0, self.0)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct IdVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for IdVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        write!(f, "{} name", stringify!($name))
+                    }
+
+                    fn visit_enum<A: serde::de::EnumAccess<'de>>(
+                        self,
+                        data: A,
+                    ) -> Result<$name, A::Error> {
+                        use serde::de::VariantAccess;
+
+                        let (name, variant): ($crate::utils::IdName, _) = data.variant()?;
+                        variant.unit_variant()?;
+                        Ok($name::new(&name.0))
+                    }
+                }
+
+                deserializer.deserialize_enum(stringify!($name), &[], IdVisitor)
+            }
+        }
+    };
+}
+
+/// An enum variant name, read with `deserialize_identifier` so that RON accepts it unquoted.
+pub struct IdName(pub String);
+
+impl<'de> serde::Deserialize<'de> for IdName {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+
+        impl serde::de::Visitor<'_> for NameVisitor {
+            type Value = IdName;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an identifier")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<IdName, E> {
+                Ok(IdName(name.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(NameVisitor)
+    }
+}
+
+pub fn intern(name: &str) -> &'static str {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut names = NAMES.get_or_init(Default::default).lock().unwrap();
+    if let Some(interned) = names.get(name) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+    names.insert(interned);
+    interned
+}
+
+/// Number of single-character insertions, deletions, substitutions and swaps of two
+/// neighbouring characters needed to turn `a` into `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            rows[i][j] = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                rows[i][j] = rows[i][j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edit_distance;
+
+    #[test]
+    fn counts_single_character_edits() {
+        assert_eq!(edit_distance("bucket", "bucket"), 0);
+        assert_eq!(edit_distance("buckt", "bucket"), 1);
+        assert_eq!(edit_distance("buckets", "bucket"), 1);
+        assert_eq!(edit_distance("bocket", "bucket"), 1);
+        assert_eq!(edit_distance("", "bucket"), 6);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn counts_a_swap_of_neighbours_as_one_edit() {
+        assert_eq!(edit_distance("bukcet", "bucket"), 1);
+        assert_eq!(edit_distance("taek", "take"), 1);
+        assert_eq!(edit_distance("ab", "ba"), 1);
+    }
+
+    #[test]
+    fn is_symmetric() {
+        for (a, b) in [("desk", "dusk"), ("look", "lok"), ("card", "cards")] {
+            assert_eq!(edit_distance(a, b), edit_distance(b, a));
+        }
+    }
+}
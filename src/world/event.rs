@@ -0,0 +1,93 @@
+use serde::Deserialize;
+
+use super::room::{Direction, PassageType, RoomIdentifier};
+use crate::entity::item::ItemId;
+
+/// Something the world data schedules to happen on its own, once the turn counter reaches `at`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorldEvent {
+    pub at: u32,
+    /// What the player notices, wherever they are.
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum Effect {
+    /// Replaces what the player sees when entering `room`.
+    Describe {
+        room: RoomIdentifier,
+        short_description: String,
+    },
+    /// Adds an exit from `room`, or replaces the one in that direction.
+    Connect {
+        room: RoomIdentifier,
+        direction: Direction,
+        passage: PassageType,
+        to: RoomIdentifier,
+    },
+    Disconnect {
+        room: RoomIdentifier,
+        direction: Direction,
+    },
+    /// Starts an alarm that repeats `message` in real time, until another alarm replaces it.
+    Alarm {
+        message: String,
+    },
+    SilenceAlarm,
+    /// Amounts added to the player's vitals; negative values take away.
+    Vitals {
+        #[serde(default)]
+        hunger: i16,
+        #[serde(default)]
+        thirst: i16,
+        #[serde(default)]
+        oxygen: i16,
+        #[serde(default)]
+        fatigue: i16,
+    },
+    /// Pushes the shuttle `speed` meters per turn towards bearing `x`, `y`, on top of how it
+    /// already drifts.
+    Drift {
+        x: i16,
+        y: i16,
+        speed: f32,
+    },
+    /// Readers stop accepting the card.
+    RevokeCard {
+        card: ItemId,
+    },
+    /// Readers can't read the card anymore.
+    DamageCard {
+        card: ItemId,
+    },
+    /// Copies the credentials of `card` onto `onto`, which also makes `onto` valid again.
+    CloneCard {
+        card: ItemId,
+        onto: ItemId,
+    },
+}
+
+impl Effect {
+    /// The rooms the effect refers to, so the loader can check they exist.
+    pub fn rooms(&self) -> Vec<RoomIdentifier> {
+        match self {
+            Effect::Describe { room, .. } | Effect::Disconnect { room, .. } => vec![*room],
+            Effect::Connect { room, to, .. } => vec![*room, *to],
+            _ => vec![],
+        }
+    }
+
+    /// The cards the effect changes, so the loader can check they are cards.
+    pub fn cards(&self) -> Vec<ItemId> {
+        match self {
+            Effect::RevokeCard { card } | Effect::DamageCard { card } => vec![*card],
+            Effect::CloneCard { card, onto } => vec![*card, *onto],
+            _ => vec![],
+        }
+    }
+}
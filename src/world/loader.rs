@@ -0,0 +1,931 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use ron::extensions::Extensions;
+use ron::Options;
+use serde::Deserialize;
+
+use super::data::World;
+use super::event::{Effect, WorldEvent};
+use super::room::{Access, Direction, Outside, PassageType, RoomAttributes, RoomIdentifier};
+use crate::entity::furniture::airlock::AirlockControls;
+use crate::entity::furniture::door::{Door, DoorState};
+use crate::entity::furniture::main_terminal::{MainTerminal, MAX_SERVICE_CODE};
+use crate::entity::furniture::navigation_computer::NavigationComputer;
+use crate::entity::furniture::sink::Sink;
+use crate::entity::furniture::Furniture;
+use crate::entity::item::card::Card;
+use crate::entity::item::container::Container;
+use crate::entity::item::food::Food;
+use crate::entity::item::garment::Garment;
+use crate::entity::item::text_item::TextItem;
+use crate::entity::item::{Drink, Item, Lid, Nourishment, Protection, Size, Slot};
+use crate::entity::{Entity, EntityId, PassiveEntity};
+
+/// Where the game looks for the world data next to its binary.
+pub const DATA_DIR: &str = "data";
+pub const ROOMS_FILE: &str = "rooms.ron";
+pub const ENTITIES_FILE: &str = "entities.ron";
+pub const EVENTS_FILE: &str = "events.ron";
+
+const BUNDLED_ROOMS: &str = include_str!("../../data/rooms.ron");
+const BUNDLED_ENTITIES: &str = include_str!("../../data/entities.ron");
+const BUNDLED_EVENTS: &str = include_str!("../../data/events.ron");
+
+/// A problem in one of the world data files, pointing at the offending line.
+#[derive(Debug)]
+pub struct DataError {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+impl std::error::Error for DataError {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoomDef {
+    id: RoomIdentifier,
+    #[serde(default)]
+    visited: bool,
+    short_description: String,
+    #[serde(default)]
+    full_description: String,
+    #[serde(default)]
+    first_thoughts: String,
+    #[serde(default)]
+    entities: Vec<EntityId>,
+    #[serde(default)]
+    connected_rooms: Vec<(Direction, PassageType, RoomIdentifier)>,
+    #[serde(default)]
+    outside: Option<Outside>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntityDef {
+    id: EntityId,
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    description: String,
+    kind: EntityKind,
+}
+
+/// Which Rust type backs an entity, together with the data only that type needs.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+enum EntityKind {
+    Passive,
+    Item {
+        size: Size,
+    },
+    Container {
+        size: Size,
+        #[serde(default)]
+        contains: Vec<EntityId>,
+        #[serde(default)]
+        lid: Lid,
+    },
+    /// `nourishes` and `quenches` are how much eating it takes off hunger and thirst.
+    Food {
+        #[serde(default)]
+        nourishes: u8,
+        #[serde(default)]
+        quenches: u8,
+    },
+    Drink {
+        #[serde(default)]
+        quenches: u8,
+    },
+    Text {
+        contents: String,
+    },
+    /// The shuttle's service receipt. `contents` can mention `{shuttle}` and `{code}`, and the
+    /// main terminal takes its maintenance code from it.
+    ServiceSlip {
+        shuttle: String,
+        code: u16,
+        contents: String,
+    },
+    /// Something to wear. `seals` keeps the vacuum out, `air` is how many turns of air its
+    /// tank holds, and `bulky` tires the wearer out.
+    Garment {
+        slot: Slot,
+        #[serde(default)]
+        seals: bool,
+        #[serde(default)]
+        air: u8,
+        #[serde(default)]
+        bulky: bool,
+    },
+    /// An access card that opens doors asking for `access` or less.
+    Card {
+        access: Access,
+    },
+    Furniture {
+        #[serde(default)]
+        contains: Vec<EntityId>,
+        #[serde(default)]
+        lid: Lid,
+    },
+    Sink {
+        #[serde(default)]
+        contains: Vec<EntityId>,
+        #[serde(default)]
+        quenches: u8,
+    },
+    /// `sides` are the rooms the door is in, each with the direction it's in from there.
+    Door {
+        access: Access,
+        #[serde(default)]
+        state: DoorState,
+        sides: Vec<(RoomIdentifier, Direction)>,
+    },
+    /// The controls of the airlock chamber `chamber`, whose door back into the shuttle is in
+    /// direction `inner` and whose door out into space is in direction `outer`.
+    Airlock {
+        chamber: RoomIdentifier,
+        inner: Direction,
+        outer: Direction,
+        #[serde(default = "pressurized")]
+        pressurized: bool,
+    },
+    /// `suspended` is what the display says until maintenance mode is on. It can mention
+    /// `{shuttle}`.
+    MainTerminal {
+        suspended: String,
+    },
+    NavigationComputer,
+}
+
+/// What the service slip says about the shuttle, for the entities that mention it.
+struct Service {
+    shuttle: String,
+    code: u16,
+}
+
+impl Service {
+    fn fill_in(&self, text: &str) -> String {
+        text.replace("{shuttle}", &self.shuttle)
+            .replace("{code}", &format!("{:04}", self.code))
+    }
+}
+
+fn pressurized() -> bool {
+    true
+}
+
+impl EntityKind {
+    fn contains(&self) -> &[EntityId] {
+        match self {
+            EntityKind::Container { contains, .. }
+            | EntityKind::Furniture { contains, .. }
+            | EntityKind::Sink { contains, .. } => contains,
+            _ => &[],
+        }
+    }
+}
+
+impl EntityDef {
+    fn build(self, service: &Service) -> Box<dyn Entity> {
+        let EntityDef {
+            id,
+            name,
+            aliases,
+            description,
+            kind,
+        } = self;
+
+        match kind {
+            EntityKind::Passive => Box::new(PassiveEntity::new(id, name, aliases, description)),
+            EntityKind::Item { size } => Box::new(Item::new(id, name, aliases, description, size)),
+            EntityKind::Container {
+                size,
+                contains,
+                lid,
+            } => Box::new(Container::new(
+                id,
+                name,
+                aliases,
+                description,
+                contains,
+                size,
+                lid,
+            )),
+            EntityKind::Food {
+                nourishes,
+                quenches,
+            } => {
+                let nourishment = Nourishment {
+                    food: nourishes,
+                    water: quenches,
+                };
+                Box::new(Food::new(id, name, aliases, description, nourishment))
+            }
+            EntityKind::Drink { quenches } => {
+                let nourishment = Nourishment {
+                    food: 0,
+                    water: quenches,
+                };
+                Box::new(Drink::new(id, name, aliases, description, nourishment))
+            }
+            EntityKind::Text { contents } => {
+                Box::new(TextItem::new(id, name, aliases, description, contents))
+            }
+            EntityKind::ServiceSlip { contents, .. } => Box::new(TextItem::new(
+                id,
+                name,
+                aliases,
+                description,
+                service.fill_in(&contents),
+            )),
+            EntityKind::Garment {
+                slot,
+                seals,
+                air,
+                bulky,
+            } => Box::new(Garment::new(
+                id,
+                name,
+                aliases,
+                description,
+                slot,
+                Protection { seals, air },
+                bulky,
+            )),
+            EntityKind::Card { access } => {
+                Box::new(Card::new(id, name, aliases, description, access))
+            }
+            EntityKind::Furniture { contains, lid } => Box::new(Furniture::new(
+                id,
+                name,
+                aliases,
+                description,
+                contains,
+                lid,
+            )),
+            EntityKind::Sink { contains, quenches } => {
+                let nourishment = Nourishment {
+                    food: 0,
+                    water: quenches,
+                };
+                Box::new(Sink::new(
+                    id,
+                    name,
+                    aliases,
+                    description,
+                    contains,
+                    nourishment,
+                ))
+            }
+            EntityKind::Door {
+                access,
+                state,
+                sides,
+            } => Box::new(Door::new(
+                id,
+                name,
+                aliases,
+                description,
+                access,
+                state,
+                sides,
+            )),
+            EntityKind::Airlock {
+                chamber,
+                inner,
+                outer,
+                pressurized,
+            } => Box::new(AirlockControls::new(
+                id,
+                name,
+                aliases,
+                description,
+                chamber,
+                (inner, outer),
+                pressurized,
+            )),
+            EntityKind::MainTerminal { suspended } => Box::new(MainTerminal::new(
+                id,
+                name,
+                aliases,
+                description,
+                service.fill_in(&suspended),
+                service.code,
+            )),
+            EntityKind::NavigationComputer => Box::new(NavigationComputer::new(
+                id,
+                name,
+                aliases,
+                description,
+                vec![],
+            )),
+        }
+    }
+}
+
+/// Loads the world shipped with the game from `data/`.
+pub fn load_bundled() -> Result<World, DataError> {
+    load_from_sources(
+        &format!("data/{}", ROOMS_FILE),
+        BUNDLED_ROOMS,
+        &format!("data/{}", ENTITIES_FILE),
+        BUNDLED_ENTITIES,
+        &format!("data/{}", EVENTS_FILE),
+        BUNDLED_EVENTS,
+    )
+}
+
+/// Loads the world from `rooms.ron`, `entities.ron` and `events.ron` in `dir`.
+pub fn load_from_dir(dir: &Path) -> Result<World, DataError> {
+    let read = |file: &str| {
+        let path = dir.join(file);
+        fs::read_to_string(&path)
+            .map(|contents| (path.display().to_string(), contents))
+            .map_err(|e| DataError {
+                file: path.display().to_string(),
+                line: 0,
+                message: e.to_string(),
+            })
+    };
+    let (rooms_file, rooms_src) = read(ROOMS_FILE)?;
+    let (entities_file, entities_src) = read(ENTITIES_FILE)?;
+    let (events_file, events_src) = read(EVENTS_FILE)?;
+
+    load_from_sources(
+        &rooms_file,
+        &rooms_src,
+        &entities_file,
+        &entities_src,
+        &events_file,
+        &events_src,
+    )
+}
+
+fn load_from_sources(
+    rooms_file: &str,
+    rooms_src: &str,
+    entities_file: &str,
+    entities_src: &str,
+    events_file: &str,
+    events_src: &str,
+) -> Result<World, DataError> {
+    let room_defs: Vec<RoomDef> = parse(rooms_file, rooms_src)?;
+    let entity_defs: Vec<EntityDef> = parse(entities_file, entities_src)?;
+    let events: Vec<WorldEvent> = parse(events_file, events_src)?;
+
+    let mut entity_ids = HashSet::new();
+    for def in &entity_defs {
+        if !entity_ids.insert(def.id) {
+            return Err(error_at(
+                entities_file,
+                entities_src,
+                &def_needle(&def.id),
+                0,
+                format!("entity `{}` is defined more than once", describe(&def.id)),
+            ));
+        }
+    }
+    let mut room_ids = HashSet::new();
+    for def in &room_defs {
+        if !room_ids.insert(def.id) {
+            return Err(error_at(
+                rooms_file,
+                rooms_src,
+                &format!("id: {}", def.id),
+                0,
+                format!("room `{}` is defined more than once", def.id),
+            ));
+        }
+    }
+
+    for def in &entity_defs {
+        let def_line = line_of(entities_src, &def_needle(&def.id), 0);
+        for contained in def.kind.contains() {
+            if !entity_ids.contains(contained) {
+                return Err(unknown_entity(
+                    entities_file,
+                    entities_src,
+                    contained,
+                    def_line,
+                ));
+            }
+        }
+    }
+    for def in &room_defs {
+        let def_line = line_of(rooms_src, &format!("id: {}", def.id), 0);
+        for entity_id in &def.entities {
+            if !entity_ids.contains(entity_id) {
+                return Err(unknown_entity(rooms_file, rooms_src, entity_id, def_line));
+            }
+        }
+        for (_, _, room_id) in &def.connected_rooms {
+            if !room_ids.contains(room_id) {
+                return Err(error_at(
+                    rooms_file,
+                    rooms_src,
+                    room_id.as_str(),
+                    def_line,
+                    format!("unknown room `{}`", room_id),
+                ));
+            }
+        }
+        if let Some(outside) = &def.outside {
+            check_outside(rooms_file, rooms_src, def, outside, &room_ids, def_line)?;
+        }
+    }
+
+    // Every door has to hang in a doorway, and every doorway needs its door.
+    let mut doors = HashSet::new();
+    for def in &entity_defs {
+        let EntityKind::Door { sides, .. } = &def.kind else {
+            continue;
+        };
+        let def_line = line_of(entities_src, &def_needle(&def.id), 0);
+        for (room_id, direction) in sides {
+            let doorway = room_defs
+                .iter()
+                .find(|room| room.id == *room_id)
+                .and_then(|room| room.connected_rooms.iter().find(|(d, _, _)| d == direction));
+            let message = match doorway {
+                _ if !room_ids.contains(room_id) => format!("unknown room `{}`", room_id),
+                Some((_, PassageType::Door, _)) if !doors.insert((*room_id, *direction)) => {
+                    format!("{} already has a door to the {}", room_id, direction)
+                }
+                Some((_, PassageType::Door, _)) => continue,
+                _ => format!("{} has no door to the {}", room_id, direction),
+            };
+            return Err(error_at(
+                entities_file,
+                entities_src,
+                room_id.as_str(),
+                def_line,
+                message,
+            ));
+        }
+    }
+    for def in &entity_defs {
+        let EntityKind::Airlock {
+            chamber,
+            inner,
+            outer,
+            ..
+        } = &def.kind
+        else {
+            continue;
+        };
+        let def_line = line_of(entities_src, &def_needle(&def.id), 0);
+        for direction in [inner, outer] {
+            if !doors.contains(&(*chamber, *direction)) {
+                return Err(error_at(
+                    entities_file,
+                    entities_src,
+                    chamber.as_str(),
+                    def_line,
+                    format!("{} has no door to the {}", chamber, direction),
+                ));
+            }
+        }
+    }
+    for def in &room_defs {
+        let def_line = line_of(rooms_src, &format!("id: {}", def.id), 0);
+        for (direction, passage_type, _) in &def.connected_rooms {
+            if *passage_type == PassageType::Door && !doors.contains(&(def.id, *direction)) {
+                return Err(error_at(
+                    rooms_file,
+                    rooms_src,
+                    &format!("({}, Door", direction),
+                    def_line,
+                    format!(
+                        "the door to the {} of {} isn't in {}",
+                        direction, def.id, ENTITIES_FILE
+                    ),
+                ));
+            }
+        }
+    }
+
+    let mut event_line = 0;
+    for event in &events {
+        event_line = line_of(events_src, &format!("at: {}", event.at), event_line);
+        for room_id in event.effects.iter().flat_map(|effect| effect.rooms()) {
+            if !room_ids.contains(&room_id) {
+                return Err(error_at(
+                    events_file,
+                    events_src,
+                    room_id.as_str(),
+                    event_line,
+                    format!("unknown room `{}`", room_id),
+                ));
+            }
+        }
+        for card in event.effects.iter().flat_map(|effect| effect.cards()) {
+            let is_card = entity_defs.iter().any(|def| {
+                def.id == EntityId::Item(card) && matches!(def.kind, EntityKind::Card { .. })
+            });
+            if !is_card {
+                return Err(error_at(
+                    events_file,
+                    events_src,
+                    card.as_str(),
+                    event_line,
+                    format!("`{}` is not a card in {}", card, ENTITIES_FILE),
+                ));
+            }
+        }
+        for effect in &event.effects {
+            if let Effect::Connect {
+                room,
+                direction,
+                passage: PassageType::Door,
+                ..
+            } = effect
+            {
+                let has_door = entity_defs.iter().any(|def| {
+                    matches!(&def.kind, EntityKind::Door { sides, .. }
+                        if sides.contains(&(*room, *direction)))
+                });
+                if !has_door {
+                    return Err(error_at(
+                        events_file,
+                        events_src,
+                        "Connect",
+                        event_line,
+                        format!(
+                            "the door to the {} of {} isn't in {}",
+                            direction, room, ENTITIES_FILE
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    let service = find_service(entities_file, entities_src, &entity_defs)?;
+
+    let rooms: HashMap<RoomIdentifier, RoomAttributes> = room_defs
+        .into_iter()
+        .map(|def| {
+            (
+                def.id,
+                RoomAttributes {
+                    room_identifier: def.id,
+                    visited: def.visited,
+                    short_description: def.short_description,
+                    full_description: def.full_description,
+                    first_thoughts: def.first_thoughts,
+                    entities: def.entities,
+                    connected_rooms: def.connected_rooms,
+                    outside: def.outside,
+                },
+            )
+        })
+        .collect();
+    let entities: HashMap<EntityId, Box<dyn Entity>> = entity_defs
+        .into_iter()
+        .map(|def| (def.id, def.build(&service)))
+        .collect();
+
+    Ok(World::new(rooms, entities, events))
+}
+
+/// The one service slip there has to be, with a code the main terminal can take.
+fn find_service(file: &str, src: &str, defs: &[EntityDef]) -> Result<Service, DataError> {
+    let mut slips = defs.iter().filter_map(|def| match &def.kind {
+        EntityKind::ServiceSlip { shuttle, code, .. } => Some((def, shuttle, *code)),
+        _ => None,
+    });
+    let Some((def, shuttle, code)) = slips.next() else {
+        let terminal_line = defs
+            .iter()
+            .find(|def| matches!(def.kind, EntityKind::MainTerminal { .. }))
+            .map_or(0, |def| line_of(src, &def_needle(&def.id), 0));
+        return Err(error_at(
+            file,
+            src,
+            "MainTerminal(",
+            terminal_line,
+            "there's no ServiceSlip for the main terminal to take its code from".to_string(),
+        ));
+    };
+    if let Some((other, _, _)) = slips.next() {
+        return Err(error_at(
+            file,
+            src,
+            &def_needle(&other.id),
+            0,
+            "there can only be one ServiceSlip".to_string(),
+        ));
+    }
+    if code > MAX_SERVICE_CODE {
+        let def_line = line_of(src, &def_needle(&def.id), 0);
+        return Err(error_at(
+            file,
+            src,
+            "code",
+            def_line,
+            format!("the service code {} has more than four digits", code),
+        ));
+    }
+    Ok(Service {
+        shuttle: shuttle.clone(),
+        code,
+    })
+}
+
+/// Walking back has to lead through the hatch, walking away mustn't, and whatever there is
+/// to catch hold of has to be a room somewhere out there.
+fn check_outside(
+    file: &str,
+    src: &str,
+    def: &RoomDef,
+    outside: &Outside,
+    room_ids: &HashSet<RoomIdentifier>,
+    def_line: usize,
+) -> Result<(), DataError> {
+    let exits = |direction: Direction| {
+        def.connected_rooms
+            .iter()
+            .any(|(exit, _, _)| *exit == direction)
+    };
+    let (needle, message) = if outside.away == outside.back {
+        (
+            "away",
+            format!(
+                "the way away from the hatch of {} is also the way back",
+                def.id
+            ),
+        )
+    } else if !exits(outside.back) {
+        (
+            "back",
+            format!(
+                "{} has no exit to the {} to get back through",
+                def.id, outside.back
+            ),
+        )
+    } else if exits(outside.away) {
+        (
+            "away",
+            format!(
+                "{} has an exit to the {}, the way out into space",
+                def.id, outside.away
+            ),
+        )
+    } else {
+        match outside.reach {
+            Some((room_id, _)) if !room_ids.contains(&room_id) => {
+                (room_id.as_str(), format!("unknown room `{}`", room_id))
+            }
+            Some((_, 0)) => (
+                "reach",
+                format!(
+                    "what there is to reach from {} has to be out in space",
+                    def.id
+                ),
+            ),
+            _ => return Ok(()),
+        }
+    };
+    Err(error_at(file, src, needle, def_line, message))
+}
+
+fn parse<'a, T: Deserialize<'a>>(file: &str, src: &'a str) -> Result<T, DataError> {
+    let options = Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+    options.from_str(src).map_err(|e| DataError {
+        file: file.to_string(),
+        line: e.position.line,
+        message: format!("{} (column {})", e.code, e.position.col),
+    })
+}
+
+fn unknown_entity(file: &str, src: &str, id: &EntityId, from_line: usize) -> DataError {
+    let needle = match id {
+        EntityId::Item(item_id) => item_id.as_str(),
+        EntityId::Furniture(furn_id) => furn_id.as_str(),
+        EntityId::Dust => "Dust",
+    };
+    error_at(
+        file,
+        src,
+        needle,
+        from_line,
+        format!("unknown entity `{}`", describe(id)),
+    )
+}
+
+fn error_at(file: &str, src: &str, needle: &str, from_line: usize, message: String) -> DataError {
+    DataError {
+        file: file.to_string(),
+        line: line_of(src, needle, from_line),
+        message,
+    }
+}
+
+/// The 1-based number of the first line at or after `from_line` that mentions `needle`.
+fn line_of(src: &str, needle: &str, from_line: usize) -> usize {
+    src.lines()
+        .enumerate()
+        .skip(from_line.saturating_sub(1))
+        .find(|(_, line)| {
+            line.match_indices(needle).any(|(i, _)| {
+                let before = line[..i].chars().next_back();
+                let after = line[i + needle.len()..].chars().next();
+                !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+                    && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+            })
+        })
+        .map(|(i, _)| i + 1)
+        .unwrap_or(from_line)
+}
+
+fn def_needle(id: &EntityId) -> String {
+    format!("id: {}", describe(id))
+}
+
+fn describe(id: &EntityId) -> String {
+    match id {
+        EntityId::Item(item_id) => format!("Item({})", item_id),
+        EntityId::Furniture(furn_id) => format!("Furniture({})", furn_id),
+        EntityId::Dust => "Dust".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::furniture::FurnId;
+
+    fn load(rooms: &str, entities: &str, events: &str) -> DataError {
+        load_from_sources(
+            ROOMS_FILE,
+            rooms,
+            ENTITIES_FILE,
+            entities,
+            EVENTS_FILE,
+            events,
+        )
+        .err()
+        .expect("the data should not load")
+    }
+
+    fn line_with(src: &str, needle: &str) -> usize {
+        src.lines().position(|line| line.contains(needle)).unwrap() + 1
+    }
+
+    #[test]
+    fn loads_the_bundled_world() {
+        assert!(load_bundled().is_ok());
+    }
+
+    #[test]
+    fn reports_malformed_ron_where_it_is() {
+        let rooms = BUNDLED_ROOMS.replacen("id: NorthMess,", "id: NorthMess", 1);
+        let error = load(&rooms, BUNDLED_ENTITIES, BUNDLED_EVENTS);
+
+        assert_eq!(error.file, ROOMS_FILE);
+        // The missing comma only shows on the next field.
+        assert_eq!(error.line, line_with(&rooms, "id: NorthMess") + 1);
+    }
+
+    #[test]
+    fn reports_unknown_fields() {
+        let entities = BUNDLED_ENTITIES.replacen("quenches: 40", "quench: 40", 1);
+        let error = load(BUNDLED_ROOMS, &entities, BUNDLED_EVENTS);
+
+        assert_eq!(error.file, ENTITIES_FILE);
+        assert_eq!(error.line, line_with(&entities, "quench: 40"));
+    }
+
+    #[test]
+    fn reports_a_dangling_room() {
+        let rooms = BUNDLED_ROOMS.replacen("(South, Free, Mess)", "(South, Free, Galley)", 1);
+        let error = load(&rooms, BUNDLED_ENTITIES, BUNDLED_EVENTS);
+
+        assert_eq!(error.file, ROOMS_FILE);
+        assert_eq!(error.line, line_with(&rooms, "Galley"));
+        assert_eq!(error.message, "unknown room `Galley`");
+    }
+
+    #[test]
+    fn reports_a_dangling_entity() {
+        let entities = BUNDLED_ENTITIES.replacen("[Item(Fork)]", "[Item(Spork)]", 1);
+        let error = load(BUNDLED_ROOMS, &entities, BUNDLED_EVENTS);
+
+        assert_eq!(error.file, ENTITIES_FILE);
+        assert_eq!(error.line, line_with(&entities, "Item(Spork)"));
+        assert_eq!(error.message, "unknown entity `Item(Spork)`");
+    }
+
+    #[test]
+    fn fills_in_the_service_record() {
+        let world = load_bundled().unwrap();
+        let terminal = world
+            .get_terminal(EntityId::Furniture(FurnId::MainTerminal))
+            .unwrap();
+
+        assert_eq!(terminal.service_code(), 4721);
+        assert!(terminal
+            .suspended()
+            .ends_with("regain control of the XM-86."));
+    }
+
+    #[test]
+    fn reports_a_service_code_with_too_many_digits() {
+        let entities = BUNDLED_ENTITIES.replacen("code: 4721", "code: 47210", 1);
+        let error = load(BUNDLED_ROOMS, &entities, BUNDLED_EVENTS);
+
+        assert_eq!(error.file, ENTITIES_FILE);
+        assert_eq!(error.line, line_with(&entities, "code: 47210"));
+        assert_eq!(
+            error.message,
+            "the service code 47210 has more than four digits"
+        );
+    }
+
+    #[test]
+    fn reports_a_missing_service_slip() {
+        let entities = BUNDLED_ENTITIES
+            .replacen("kind: ServiceSlip(", "kind: Text(", 1)
+            .replacen(
+                "            shuttle: \"XM-86\",\n            code: 4721,\n",
+                "",
+                1,
+            );
+        let error = load(BUNDLED_ROOMS, &entities, BUNDLED_EVENTS);
+
+        assert_eq!(error.file, ENTITIES_FILE);
+        assert_eq!(error.line, line_with(&entities, "kind: MainTerminal"));
+        assert_eq!(
+            error.message,
+            "there's no ServiceSlip for the main terminal to take its code from"
+        );
+    }
+
+    #[test]
+    fn reports_open_space_without_a_way_back() {
+        let rooms =
+            BUNDLED_ROOMS.replacen("(away: East, back: West)", "(away: East, back: North)", 1);
+        let error = load(&rooms, BUNDLED_ENTITIES, BUNDLED_EVENTS);
+
+        assert_eq!(error.file, ROOMS_FILE);
+        assert_eq!(error.line, line_with(&rooms, "back: North"));
+        assert_eq!(
+            error.message,
+            "OpenSpaceAirlockB has no exit to the North to get back through"
+        );
+    }
+
+    #[test]
+    fn reports_open_space_reaching_an_unknown_room() {
+        let rooms = BUNDLED_ROOMS.replacen("(StationAirlock, 8)", "(Moon, 8)", 1);
+        let error = load(&rooms, BUNDLED_ENTITIES, BUNDLED_EVENTS);
+
+        assert_eq!(error.file, ROOMS_FILE);
+        assert_eq!(error.line, line_with(&rooms, "(Moon, 8)"));
+        assert_eq!(error.message, "unknown room `Moon`");
+    }
+
+    /// Writes the bundled files into a fresh directory of their own, with `rooms` in place of
+    /// `rooms.ron`.
+    fn data_dir(name: &str, rooms: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("voidlogue-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(ROOMS_FILE), rooms).unwrap();
+        fs::write(dir.join(ENTITIES_FILE), BUNDLED_ENTITIES).unwrap();
+        fs::write(dir.join(EVENTS_FILE), BUNDLED_EVENTS).unwrap();
+        dir
+    }
+
+    #[test]
+    fn loads_a_world_from_a_directory() {
+        let rooms = BUNDLED_ROOMS.replacen("It is quite a small room.", "A cramped room.", 1);
+        let dir = data_dir("loads", &rooms);
+        let world = load_from_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let storage = &world.unwrap().rooms[&RoomIdentifier::Storage];
+        assert!(storage.short_description.starts_with("A cramped room."));
+    }
+
+    #[test]
+    fn reports_errors_in_a_directory_with_their_path() {
+        let rooms = BUNDLED_ROOMS.replacen("(South, Free, Mess)", "(South, Free, Galley)", 1);
+        let dir = data_dir("errors", &rooms);
+        let error = load_from_dir(&dir).err().expect("the data should not load");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(error.file, dir.join(ROOMS_FILE).display().to_string());
+        assert_eq!(error.line, line_with(&rooms, "Galley"));
+    }
+}
@@ -1,2 +1,4 @@
 pub mod data;
+pub mod event;
+pub mod loader;
 pub mod room;
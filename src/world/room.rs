@@ -1,33 +1,20 @@
-use crate::entity::{furniture::Furniture, EntityId};
+use crate::entity::EntityId;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use strum_macros::{Display, EnumIter};
+use strum_macros::Display;
 
-#[derive(EnumIter, Debug, Eq, PartialEq, Hash, Clone, Copy)]
-pub enum RoomIdentifier {
-    Storage,
-    NorthMess,
-    Mess,
-    SouthMess,
-    MeetingRoom,
-    Bridge,
-    PassengersRoom,
-    AirlockCorridor,
-    AirlockA,
-    OpenSpaceAirlockA,
-    AirlockB,
-    OpenSpaceAirlockB,
-    TechCorridor,
-    TechCorridorNorth,
-    EngineRoom,
-    BosunsRoom,
-    CaptainsRoom,
-    StorageHold,
-    CrewCabins,
+string_id!(RoomIdentifier);
 
-    StationAirlock,
+/// Rooms the engine itself refers to. Every other room only exists in the world data.
+#[allow(non_upper_case_globals)]
+impl RoomIdentifier {
+    pub const Storage: RoomIdentifier = RoomIdentifier("Storage");
+    pub const Bridge: RoomIdentifier = RoomIdentifier("Bridge");
+    pub const AirlockA: RoomIdentifier = RoomIdentifier("AirlockA");
+    pub const StationAirlock: RoomIdentifier = RoomIdentifier("StationAirlock");
 }
 
-#[derive(Display, PartialEq, Clone, Copy)]
+#[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum Direction {
     North,
     East,
@@ -36,7 +23,8 @@ pub enum Direction {
     Up,
     Down,
 }
-#[derive(Debug, PartialEq, Eq)]
+/// What a card reader asks for. A is the highest clearance; a broken reader accepts nothing.
+#[derive(Debug, Display, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum Access {
     A,
     B,
@@ -46,7 +34,7 @@ pub enum Access {
     None,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum PassageType {
     Door,
     Free,
@@ -77,13 +65,27 @@ impl Ord for Access {
     }
 }
 
+/// Open space right outside one of the shuttle's hatches.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Outside {
+    /// The way from the hatch out into space.
+    pub away: Direction,
+    /// The way back to the hatch.
+    pub back: Direction,
+    /// Somewhere to catch hold of out there, and how many lengths from the hatch it is.
+    #[serde(default)]
+    pub reach: Option<(RoomIdentifier, u8)>,
+}
+
 pub struct RoomAttributes {
     pub room_identifier: RoomIdentifier,
     pub visited: bool,
-    pub access: Access,
     pub short_description: String,
     pub full_description: String,
     pub first_thoughts: String,
     pub entities: Vec<EntityId>,
     pub connected_rooms: Vec<(Direction, PassageType, RoomIdentifier)>,
+    /// Set for rooms out in open space, where the player is on a spacewalk.
+    pub outside: Option<Outside>,
 }
@@ -0,0 +1,117 @@
+//! Plays the walkthroughs in `tests/scripts` through the game binary and compares the
+//! transcripts with the ones in `tests/golden`. Run with `UPDATE_GOLDEN=1` to rewrite them.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use voidlogue::engine::script::{EXIT_DIED, EXIT_LOST_IN_SPACE, EXIT_SCRIPT_END, EXIT_WON};
+
+// Flavor text is picked at random, so the scripts always run with the same seed.
+const SEED: &str = "39";
+
+fn play(name: &str) -> (i32, String) {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let script = root.join("tests/scripts").join(format!("{name}.txt"));
+    let output = Command::new(env!("CARGO_BIN_EXE_voidlogue"))
+        .args(["--seed", SEED])
+        .arg("--script")
+        .arg(&script)
+        .current_dir(&root)
+        .output()
+        .expect("failed to run the game");
+
+    let transcript = String::from_utf8(output.stdout).expect("transcript is not UTF-8");
+    (output.status.code().expect("game was killed"), transcript)
+}
+
+/// Each script with the exit code its game ends with.
+const SCRIPTS: &[(&str, i32)] = &[
+    // The full docking walkthrough.
+    ("walkthrough", EXIT_WON),
+    // Coming in sideways bounces off the port; turning and burning again docks.
+    ("docking", EXIT_SCRIPT_END),
+    // Eating and drinking show in status.
+    ("vitals", EXIT_SCRIPT_END),
+    // Waiting runs into scheduled events.
+    ("clock", EXIT_SCRIPT_END),
+    // Waiting without eating starves.
+    ("starvation", EXIT_DIED),
+    // Closed containers hide their contents.
+    ("lids", EXIT_SCRIPT_END),
+    // Doors check cards and remember their state.
+    ("doors", EXIT_SCRIPT_END),
+    // Cards open the doors their readers accept.
+    ("cards", EXIT_SCRIPT_END),
+    // Wearing the suit gets you to the station.
+    ("suit", EXIT_WON),
+    // Carrying the suit does not protect you.
+    ("vacuum", EXIT_DIED),
+    // Airlocks cycle before the outer door opens.
+    ("airlock", EXIT_SCRIPT_END),
+    // Drifting into the station too fast gets you lost.
+    ("eva", EXIT_LOST_IN_SPACE),
+    // A second burn corrects a bad approach.
+    ("flight", EXIT_SCRIPT_END),
+    // Burns draw fuel from the selected tank.
+    ("fuel", EXIT_SCRIPT_END),
+    // The terminal lists its commands and explains bad codes.
+    ("terminal", EXIT_SCRIPT_END),
+    // Using the terminal opens a shell session.
+    ("session", EXIT_SCRIPT_END),
+];
+
+/// Compares `transcript` with the golden one, or rewrites that with `UPDATE_GOLDEN`.
+fn check_golden(name: &str, transcript: &str) -> Result<(), String> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, transcript).expect("failed to write the golden transcript");
+        return Ok(());
+    }
+
+    let golden =
+        fs::read_to_string(&path).map_err(|_| format!("{name}: missing golden transcript"))?;
+    if golden != transcript {
+        return Err(format!(
+            "transcript of {name} differs from {}; rerun with UPDATE_GOLDEN=1 if that's intended\n\n{transcript}",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn scripts_play_as_recorded() {
+    let failures: Vec<String> = SCRIPTS
+        .iter()
+        .filter_map(|(name, expected_exit)| {
+            let (code, transcript) = play(name);
+            if code != *expected_exit {
+                return Some(format!(
+                    "{name} exited with {code} instead of {expected_exit}"
+                ));
+            }
+            check_golden(name, &transcript).err()
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+#[test]
+fn every_script_is_played() {
+    let scripts = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/scripts");
+    for entry in fs::read_dir(scripts).expect("missing tests/scripts") {
+        let path = entry.expect("can't list tests/scripts").path();
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        assert!(
+            SCRIPTS.iter().any(|(script, _)| *script == name),
+            "tests/scripts/{name}.txt isn't in SCRIPTS"
+        );
+    }
+}